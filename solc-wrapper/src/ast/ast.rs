@@ -1,6 +1,5 @@
 use std::collections::HashMap;
-use std::fs::File;
-use serde::{Serialize, Deserialize};
+use serde::{Serialize, Deserialize, Deserializer};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StateMutability {
@@ -19,6 +18,15 @@ pub enum StateMutability {
 
 pub type SourceLocation = String;
 
+// Builtins (`this`, `msg`, `require`...) are referenced by negative ids, they have no declaration in the AST
+fn declaration_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<usize>, D::Error> {
+    Ok(Option::<i64>::deserialize(deserializer)?.and_then(|id| usize::try_from(id).ok()))
+}
+
+fn declaration_ids<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<usize>, D::Error> {
+    Ok(Vec::<i64>::deserialize(deserializer)?.into_iter().filter_map(|id| usize::try_from(id).ok()).collect())
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Mutability {
     #[serde(rename = "mutable")]
@@ -420,7 +428,7 @@ pub struct SourceUnit {
     #[serde(rename = "absolutePath")]
     pub absolute_path: String,
     #[serde(rename = "exportedSymbols")]
-    pub exported_symbols: Option<HashMap<String, Vec<String>>>,
+    pub exported_symbols: Option<HashMap<String, Vec<usize>>>,
     #[serde(rename = "license", skip_serializing_if = "Option::is_none")]
     pub license: Option<String>,
     #[serde(rename = "nodes")]
@@ -677,9 +685,9 @@ pub struct Identifier {
     #[serde(rename = "argumentTypes", skip_serializing_if = "Option::is_none")]
     pub argument_types: Option<Vec<TypeDescriptions>>,
     pub name: String,
-    #[serde(rename = "overloadedDeclarations")]
+    #[serde(rename = "overloadedDeclarations", deserialize_with = "declaration_ids")]
     pub overloaded_declarations: Vec<usize>,
-    #[serde(rename = "referencedDeclaration")]
+    #[serde(rename = "referencedDeclaration", default, deserialize_with = "declaration_id")]
     pub referenced_declaration: Option<usize>,
     #[serde(rename = "typeDescriptions")]
     pub type_descriptions: TypeDescriptions,
@@ -785,7 +793,7 @@ pub struct MemberAccess {
     pub member_location: SourceLocation,
     #[serde(rename = "memberName")]
     pub member_name: String,
-    #[serde(rename = "referencedDeclaration")]
+    #[serde(rename = "referencedDeclaration", default, deserialize_with = "declaration_id")]
     pub referenced_declaration: Option<usize>,
     #[serde(rename = "nodeType")]
     pub node_type: NodeType,
@@ -828,7 +836,7 @@ pub struct ArrayTypeName {
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionTypeName {
-    id : usize,
+    pub id: usize,
     pub src: SourceLocation,
     #[serde(rename = "typeDescriptions")]
     pub type_descriptions: TypeDescriptions,
@@ -1460,13 +1468,13 @@ pub fn get_line_from_offset(content: &str, offset: usize) -> (usize, usize) {
         }
         return (nb_line, tmp);
     }
-    return (0, 0);
+    (0, 0)
 }
 
 pub fn decode_begin_location(src: &str, content: &str) -> CodeLocation {
     let mut split = src.split(':');
     let offset = split.next().unwrap().parse().unwrap();
-    let (line, column) = get_line_from_offset(&content, offset);
+    let (line, column) = get_line_from_offset(content, offset);
     let length = split.next().unwrap().parse().unwrap();
     CodeLocation { line, column, length }
 }
@@ -1474,7 +1482,7 @@ pub fn decode_begin_location(src: &str, content: &str) -> CodeLocation {
 pub fn decode_end_location(src: &str, content: &str) -> CodeLocation {
     let mut split = src.split(':');
     let offset = split.next().unwrap().parse().unwrap();
-    let (line, _column) = get_line_from_offset(&content, offset);
+    let (line, _column) = get_line_from_offset(content, offset);
     let length = split.next().unwrap().parse().unwrap();
     let extract = content[offset..offset + length].to_string();
     let (diff_line, new_column) = get_line_from_offset(&extract, length);
@@ -1507,12 +1515,13 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_correct_EnumValue_parsing() -> Result<(), String> {
+    fn test_correct_enum_value_parsing() -> Result<(), String> {
         let ast = fs::read_to_string("../solc-wrapper/tests/files/ast/EnumValue.json").expect("Could not find test data file");
         let res = serde_json::from_str::<EnumValue>(&ast).map_err(|_| "Error deserializing EnumValue".to_string())?;
         assert_eq!(res.name, "item1");
         assert_eq!(res.name_location, Some("72:5:0".to_string()));
-        Ok(assert_eq!(res.node_type, NodeType::EnumValue))
+        assert_eq!(res.node_type, NodeType::EnumValue);
+        Ok(())
     }
 
     #[test]
@@ -1521,19 +1530,21 @@ mod tests {
         let res = serde_json::from_str::<EnumDefinition>(&ast).map_err(|_| "Error deserializing EnumDefinition".to_string())?;
         assert_eq!(res.canonical_name, "Test");
         assert_eq!(res.name_location, Some("66:4:0".to_string()));
-        Ok(assert_eq!(res.node_type, NodeType::EnumDefinition))
+        assert_eq!(res.node_type, NodeType::EnumDefinition);
+        Ok(())
     }
 
     #[test]
     fn test_correct_event_definition_parsing() -> Result<(), String> {
         let ast = fs::read_to_string("../solc-wrapper/tests/files/ast/EventDefinition.json").expect("Could not find test data file");
         let res = serde_json::from_str::<EventDefinition>(&ast).map_err(|_| "Error deserializing EventDefinition".to_string())?;
-        assert_eq!(res.anonymous, false);
+        assert!(!res.anonymous);
         assert_eq!(res.id, 67);
         assert_eq!(res.name, "MintingLocked".to_string());
         assert_eq!(res.src, "1582:45:0".to_string());
         assert_eq!(res.name_location, Some("1588:13:0".to_string()));
-        Ok(assert_eq!(res.node_type, NodeType::EventDefinition))
+        assert_eq!(res.node_type, NodeType::EventDefinition);
+        Ok(())
     }
 
     #[test]
@@ -1542,7 +1553,8 @@ mod tests {
         let res = serde_json::from_str::<Return>(&ast).map_err(|_| "Error deserializing Return".to_string())?;
         assert_eq!(res.id, 296);
         assert_eq!(res.src, "5761:19:0".to_string());
-        Ok(assert_eq!(res.node_type, NodeType::Return))
+        assert_eq!(res.node_type, NodeType::Return);
+        Ok(())
     }
 
     #[test]
@@ -1551,7 +1563,8 @@ mod tests {
         let res = serde_json::from_str::<EmitStatement>(&ast).map_err(|_| "Error deserializing EmitStatement".to_string())?;
         assert_eq!(res.id, 287);
         assert_eq!(res.src, "5537:33:0".to_string());
-        Ok(assert_eq!(res.node_type, NodeType::EmitStatement))
+        assert_eq!(res.node_type, NodeType::EmitStatement);
+        Ok(())
     }
 
     #[test]
@@ -1561,7 +1574,8 @@ mod tests {
 
         assert_eq!(res.id, 274);
         assert_eq!(res.src, "5445:13:0".to_string());
-        Ok(assert_eq!(res.node_type, NodeType::ModifierInvocation))
+        assert_eq!(res.node_type, NodeType::ModifierInvocation);
+        Ok(())
     }
 
     #[test]
@@ -1573,7 +1587,8 @@ mod tests {
         assert_eq!(res.literals, vec!["solidity".to_string(),
                                       "0.8".to_string(),
                                       ".16".to_string()] as Vec<String>);
-        Ok(assert_eq!(res.node_type, NodeType::PragmaDirective))
+        assert_eq!(res.node_type, NodeType::PragmaDirective);
+        Ok(())
     }
 
     #[test]
@@ -1582,8 +1597,9 @@ mod tests {
         let res = serde_json::from_str::<TupleExpression>(&ast).map_err(|_| "Error deserializing TupleExpression".to_string())?;
         assert_eq!(res.id, 16);
         assert_eq!(res.src, "150:12:0".to_string());
-        assert_eq!(res.is_inline_array, false);
-        Ok(assert_eq!(res.node_type, NodeType::TupleExpression))
+        assert!(!res.is_inline_array);
+        assert_eq!(res.node_type, NodeType::TupleExpression);
+        Ok(())
     }
 
     #[test]
@@ -1593,7 +1609,8 @@ mod tests {
         assert_eq!(res.id, 31);
         assert_eq!(res.global, Some(false));
         assert_eq!(res.src, "1007:36:0".to_string());
-        Ok(assert_eq!(res.node_type, NodeType::UsingForDirective))
+        assert_eq!(res.node_type, NodeType::UsingForDirective);
+        Ok(())
     }
 
     #[test]
@@ -1604,9 +1621,10 @@ mod tests {
         assert_eq!(res.name, "metadataNotLocked".to_string());
         assert_eq!(res.name_location, Some("1995:17:0".to_string()));
         assert_eq!(res.src, "1986:104:0".to_string());
-        assert_eq!(res.is_virtual, false);
+        assert!(!res.is_virtual);
         assert_eq!(res.visibility, Visibility::Internal);
-        Ok(assert_eq!(res.node_type, NodeType::ModifierDefinition))
+        assert_eq!(res.node_type, NodeType::ModifierDefinition);
+        Ok(())
     }
 
     #[test]
@@ -1615,7 +1633,8 @@ mod tests {
         let res = serde_json::from_str::<ParameterList>(&ast).map_err(|_| "Error deserializing ParameterList".to_string())?;
         assert_eq!(res.id, 197);
         assert_eq!(res.src, "3904:30:0".to_string());
-        Ok(assert_eq!(res.node_type, NodeType::ParameterList))
+        assert_eq!(res.node_type, NodeType::ParameterList);
+        Ok(())
     }
 
     #[test]
@@ -1626,7 +1645,8 @@ mod tests {
         assert_eq!(res.id, 141);
         assert_eq!(res.src, "2849:35:0".to_string());
         assert_eq!(res.operator, AssignmentOperator::Equal);
-        Ok(assert_eq!(res.node_type, NodeType::Assignment))
+        assert_eq!(res.node_type, NodeType::Assignment);
+        Ok(())
     }
 
     #[test]
@@ -1636,22 +1656,24 @@ mod tests {
 
         assert_eq!(res.id, 13);
         assert_eq!(res.src, "828:16:0".to_string());
-        Ok(assert_eq!(res.node_type, NodeType::InheritanceSpecifier))
+        assert_eq!(res.node_type, NodeType::InheritanceSpecifier);
+        Ok(())
     }
 
     #[test]
     fn test_correct_variable_declaration_parsing() -> Result<(), String> {
         let ast = fs::read_to_string("../solc-wrapper/tests/files/ast/VariableDeclaration.json").expect("Could not find test data file");
         let res = serde_json::from_str::<VariableDeclaration>(&ast).map_err(|_| "Error deserializing VariableDeclaration".to_string())?;
-        assert_eq!(res.is_constant, false);
+        assert!(!res.is_constant);
         assert_eq!(res.id, 293);
         assert_eq!(res.mutability, Mutability::Mutable);
         assert_eq!(res.src, "5736:13:0".to_string());
         assert_eq!(res.name_location, Some("-1:-1:-1".to_string()));
-        assert_eq!(res.state_variable, false);
+        assert!(!res.state_variable);
         assert_eq!(res.storage_location, StorageLocation::Memory);
         assert_eq!(res.visibility, Visibility::Internal);
-        Ok(assert_eq!(res.node_type, NodeType::VariableDeclaration))
+        assert_eq!(res.node_type, NodeType::VariableDeclaration);
+        Ok(())
     }
 
     #[test]
@@ -1660,7 +1682,8 @@ mod tests {
         let res = serde_json::from_str::<IfStatement>(&ast).map_err(|_| "Error deserializing IfStatement".to_string())?;
         assert_eq!(res.id, 19);
         assert_eq!(res.src, "145:103:0".to_string());
-        Ok(assert_eq!(res.node_type, NodeType::IfStatement))
+        assert_eq!(res.node_type, NodeType::IfStatement);
+        Ok(())
     }
 
     #[test]
@@ -1671,12 +1694,13 @@ mod tests {
         assert_eq!(res.id, 18);
         assert_eq!(res.src, "203:5:0".to_string());
         assert_eq!(res.argument_types, None);
-        assert_eq!(res.is_constant, false);
-        assert_eq!(res.is_l_value, false);
-        assert_eq!(res.is_pure, false);
-        assert_eq!(res.l_value_requested, false);
+        assert!(!res.is_constant);
+        assert!(!res.is_l_value);
+        assert!(!res.is_pure);
+        assert!(!res.l_value_requested);
         assert_eq!(res.operator, BinaryOperator::Ampersand);
-        Ok(assert_eq!(res.node_type, NodeType::BinaryOperation))
+        assert_eq!(res.node_type, NodeType::BinaryOperation);
+        Ok(())
     }
 
     #[test]
@@ -1687,8 +1711,9 @@ mod tests {
         assert_eq!(res.id, 7);
         assert_eq!(res.src, "104:6:0".to_string());
         assert_eq!(res.operator, UnaryOperator::DoublePlus);
-        assert_eq!(res.prefix, false);
-        Ok(assert_eq!(res.node_type, NodeType::UnaryOperation))
+        assert!(!res.prefix);
+        assert_eq!(res.node_type, NodeType::UnaryOperation);
+        Ok(())
     }
 
     #[test]
@@ -1698,7 +1723,8 @@ mod tests {
 
         assert_eq!(res.id, 9);
         assert_eq!(res.src, "104:21:0".to_string());
-        Ok(assert_eq!(res.node_type, NodeType::UncheckedBlock))
+        assert_eq!(res.node_type, NodeType::UncheckedBlock);
+        Ok(())
     }
 
     #[test]
@@ -1711,7 +1737,20 @@ mod tests {
         assert_eq!(res.name, "a".to_string());
         assert_eq!(res.overloaded_declarations, vec![] as Vec<usize>);
         assert_eq!(res.referenced_declaration, Some(7));
-        Ok(assert_eq!(res.node_type, NodeType::Identifier))
+
+        let builtin = serde_json::from_value::<Identifier>(serde_json::json!({
+            "id": 17,
+            "src": "210:7:0",
+            "name": "require",
+            "overloadedDeclarations": [-18, -19],
+            "referencedDeclaration": -18,
+            "typeDescriptions": {},
+            "nodeType": "Identifier",
+        })).map_err(|e| e.to_string())?;
+        assert_eq!(builtin.overloaded_declarations, vec![] as Vec<usize>);
+        assert_eq!(builtin.referenced_declaration, None);
+        assert_eq!(res.node_type, NodeType::Identifier);
+        Ok(())
     }
 
     #[test]
@@ -1721,10 +1760,10 @@ mod tests {
 
         assert_eq!(res.id, 10);
         assert_eq!(res.src, "158:20:0".to_string());
-        assert_eq!(res.is_constant, false);
-        assert_eq!(res.is_l_value, false);
-        assert_eq!(res.is_pure, true);
-        assert_eq!(res.l_value_requested, false);
+        assert!(!res.is_constant);
+        assert!(!res.is_l_value);
+        assert!(res.is_pure);
+        assert!(!res.l_value_requested);
         assert_eq!(res.node_type, NodeType::Conditional);
         Ok(())
     }
@@ -1759,7 +1798,7 @@ mod tests {
 
         assert_eq!(res.id, 78);
         assert_eq!(res.src, "1850:44:0".to_string());
-        assert_eq!(res.try_call, false);
+        assert!(!res.try_call);
         assert_eq!(res.node_type, NodeType::FunctionCall);
         Ok(())
     }
@@ -1835,7 +1874,7 @@ mod tests {
         assert_eq!(res.name_location, Some("792:28:0".to_string()));
         assert_eq!(res.name, "StartonERC721MetaTransaction".to_string());
         assert_eq!(res.contract_kind, ContractKind::Contract);
-        assert_eq!(res.is_abstract, false);
+        assert!(!res.is_abstract);
         assert_eq!(res.node_type, NodeType::ContractDefinition);
         Ok(())
     }
@@ -1849,10 +1888,10 @@ mod tests {
         assert_eq!(res.src, "3392:267:0".to_string());
         assert_eq!(res.name, "mint".to_string());
         assert_eq!(res.name_location, Some("3401:4:0".to_string()));
-        assert_eq!(res.implemented, true);
+        assert!(res.implemented);
         assert_eq!(res.kind, FunctionDefinitionKind::Function);
         assert_eq!(res.visibility, Some(Visibility::Public));
-        assert_eq!(res.is_virtual, false);
+        assert!(!res.is_virtual);
         assert_eq!(res.state_mutability, StateMutability::NonPayable);
         assert_eq!(res.node_type, NodeType::FunctionDefinition);
         Ok(())
@@ -0,0 +1,521 @@
+//! Index of the nodes of one or several source units by their solc `id`, with the parent of each node.
+
+use std::collections::HashMap;
+use crate::ast::ast::*;
+
+macro_rules! node_ref {
+    ($($node:ident),* $(,)?) => {
+        /// A reference to any node of the AST having an `id`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum NodeRef<'a> {
+            $($node(&'a $node),)*
+        }
+
+        impl<'a> NodeRef<'a> {
+            pub fn id(&self) -> usize {
+                match self {
+                    $(NodeRef::$node(node) => node.id,)*
+                }
+            }
+
+            pub fn src(&self) -> &'a str {
+                match self {
+                    $(NodeRef::$node(node) => &node.src,)*
+                }
+            }
+
+            pub fn node_type(&self) -> &'a NodeType {
+                match self {
+                    $(NodeRef::$node(node) => &node.node_type,)*
+                }
+            }
+        }
+
+        $(
+            impl<'a> From<&'a $node> for NodeRef<'a> {
+                fn from(node: &'a $node) -> Self {
+                    NodeRef::$node(node)
+                }
+            }
+        )*
+    };
+}
+
+node_ref!(
+    SourceUnit,
+    PragmaDirective,
+    ImportDirective,
+    UsingForDirective,
+    ContractDefinition,
+    InheritanceSpecifier,
+    FunctionDefinition,
+    ModifierDefinition,
+    ModifierInvocation,
+    StructDefinition,
+    EnumDefinition,
+    EnumValue,
+    ErrorDefinition,
+    EventDefinition,
+    UserDefinedValueTypeDefinition,
+    VariableDeclaration,
+    ParameterList,
+    OverrideSpecifier,
+    StructuredDocumentation,
+    ArrayTypeName,
+    ElementaryTypeName,
+    FunctionTypeName,
+    Mapping,
+    UserDefinedTypeName,
+    IdentifierPath,
+    Block,
+    Break,
+    Continue,
+    DoWhileStatement,
+    EmitStatement,
+    ExpressionStatement,
+    ForStatement,
+    IfStatement,
+    InlineAssembly,
+    PlaceholderStatement,
+    Return,
+    RevertStatement,
+    TryStatement,
+    TryCatchClause,
+    UncheckedBlock,
+    VariableDeclarationStatement,
+    WhileStatement,
+    Assignment,
+    BinaryOperation,
+    Conditional,
+    ElementaryTypeNameExpression,
+    FunctionCall,
+    FunctionCallOptions,
+    Identifier,
+    IndexAccess,
+    IndexRangeAccess,
+    Literal,
+    MemberAccess,
+    NewExpression,
+    TupleExpression,
+    UnaryOperation,
+);
+
+impl<'a> From<&'a Expression> for NodeRef<'a> {
+    fn from(node: &'a Expression) -> Self {
+        match node {
+            Expression::Assignment(node) => NodeRef::Assignment(node),
+            Expression::BinaryOperation(node) => NodeRef::BinaryOperation(node),
+            Expression::Conditional(node) => NodeRef::Conditional(node),
+            Expression::ElementaryTypeNameExpression(node) => NodeRef::ElementaryTypeNameExpression(node),
+            Expression::FunctionCall(node) => NodeRef::FunctionCall(node),
+            Expression::FunctionCallOptions(node) => NodeRef::FunctionCallOptions(node),
+            Expression::Identifier(node) => NodeRef::Identifier(node),
+            Expression::IdentifierPath(node) => NodeRef::IdentifierPath(node),
+            Expression::IndexAccess(node) => NodeRef::IndexAccess(node),
+            Expression::IndexRangeAccess(node) => NodeRef::IndexRangeAccess(node),
+            Expression::Literal(node) => NodeRef::Literal(node),
+            Expression::MemberAccess(node) => NodeRef::MemberAccess(node),
+            Expression::NewExpression(node) => NodeRef::NewExpression(node),
+            Expression::TupleExpression(node) => NodeRef::TupleExpression(node),
+            Expression::UnaryOperation(node) => NodeRef::UnaryOperation(node),
+        }
+    }
+}
+
+impl<'a> From<&'a Statement> for NodeRef<'a> {
+    fn from(node: &'a Statement) -> Self {
+        match node {
+            Statement::VariableDeclarationStatement(node) => NodeRef::VariableDeclarationStatement(node),
+            Statement::ForStatement(node) => NodeRef::ForStatement(node),
+            Statement::IfStatement(node) => NodeRef::IfStatement(node),
+            Statement::DoWhileStatement(node) => NodeRef::DoWhileStatement(node),
+            Statement::Return(node) => NodeRef::Return(node),
+            Statement::TryStatement(node) => NodeRef::TryStatement(node),
+            Statement::WhileStatement(node) => NodeRef::WhileStatement(node),
+            Statement::UncheckedBlock(node) => NodeRef::UncheckedBlock(node),
+            Statement::EmitStatement(node) => NodeRef::EmitStatement(node),
+            Statement::RevertStatement(node) => NodeRef::RevertStatement(node),
+            Statement::ExpressionStatement(node) => NodeRef::ExpressionStatement(node),
+            Statement::Block(node) => NodeRef::Block(node),
+            Statement::Continue(node) => NodeRef::Continue(node),
+            Statement::Break(node) => NodeRef::Break(node),
+            Statement::PlaceholderStatement(node) => NodeRef::PlaceholderStatement(node),
+        }
+    }
+}
+
+impl<'a> From<&'a TypeName> for NodeRef<'a> {
+    fn from(node: &'a TypeName) -> Self {
+        match node {
+            TypeName::ArrayTypeName(node) => NodeRef::ArrayTypeName(node),
+            TypeName::ElementaryTypeName(node) => NodeRef::ElementaryTypeName(node),
+            TypeName::FunctionTypeName(node) => NodeRef::FunctionTypeName(node),
+            TypeName::Mapping(node) => NodeRef::Mapping(node),
+            TypeName::UserDefinedTypeName(node) => NodeRef::UserDefinedTypeName(node),
+        }
+    }
+}
+
+impl<'a> From<&'a Body> for NodeRef<'a> {
+    fn from(node: &'a Body) -> Self {
+        match node {
+            Body::Block(node) => NodeRef::Block(node),
+            Body::Statement(node) => NodeRef::from(&**node),
+        }
+    }
+}
+
+impl<'a> NodeRef<'a> {
+    /// The name of a declaration, or of the declaration referenced by an identifier.
+    pub fn name(&self) -> Option<&'a str> {
+        match self {
+            NodeRef::ContractDefinition(node) => Some(&node.name),
+            NodeRef::FunctionDefinition(node) => Some(&node.name),
+            NodeRef::ModifierDefinition(node) => Some(&node.name),
+            NodeRef::StructDefinition(node) => Some(&node.name),
+            NodeRef::EnumDefinition(node) => Some(&node.name),
+            NodeRef::EnumValue(node) => Some(&node.name),
+            NodeRef::ErrorDefinition(node) => Some(&node.name),
+            NodeRef::EventDefinition(node) => Some(&node.name),
+            NodeRef::UserDefinedValueTypeDefinition(node) => Some(&node.name),
+            NodeRef::VariableDeclaration(node) => Some(&node.name),
+            NodeRef::Identifier(node) => Some(&node.name),
+            NodeRef::IdentifierPath(node) => Some(&node.name),
+            NodeRef::MemberAccess(node) => Some(&node.member_name),
+            NodeRef::UserDefinedTypeName(node) => match &node.path_node {
+                Some(path) => Some(&path.name),
+                None => node.name.as_deref(),
+            },
+            _ => None,
+        }
+    }
+
+    /// The id of the declaration referenced by the node, only set by solc once the code is analysed.
+    pub fn referenced_declaration(&self) -> Option<usize> {
+        match self {
+            NodeRef::Identifier(node) => node.referenced_declaration,
+            NodeRef::IdentifierPath(node) => node.referenced_declaration,
+            NodeRef::MemberAccess(node) => node.referenced_declaration,
+            NodeRef::UserDefinedTypeName(node) => node.referenced_declaration
+                .or_else(|| node.path_node.as_ref().and_then(|path| path.referenced_declaration)),
+            NodeRef::ImportDirective(node) => node.source_unit,
+            _ => None,
+        }
+    }
+
+    /// The direct children of the node, in the order of the source.
+    pub fn children(&self) -> Vec<NodeRef<'a>> {
+        let mut children: Vec<NodeRef<'a>> = Vec::new();
+
+        match *self {
+            NodeRef::SourceUnit(node) => {
+                for child in &node.nodes {
+                    match child {
+                        SourceUnitChildNodes::ContractDefinition(child) => children.push(NodeRef::ContractDefinition(child)),
+                        SourceUnitChildNodes::StructDefinition(child) => children.push(NodeRef::StructDefinition(child)),
+                        SourceUnitChildNodes::EnumDefinition(child) => children.push(NodeRef::EnumDefinition(child)),
+                        SourceUnitChildNodes::ErrorDefinition(child) => children.push(NodeRef::ErrorDefinition(child)),
+                        SourceUnitChildNodes::PragmaDirective(child) => children.push(NodeRef::PragmaDirective(child)),
+                        SourceUnitChildNodes::ImportDirective(child) => children.push(NodeRef::ImportDirective(child)),
+                        SourceUnitChildNodes::UsingForDirective(child) => children.push(NodeRef::UsingForDirective(child)),
+                        SourceUnitChildNodes::Other(_) => {}
+                    }
+                }
+            }
+            NodeRef::ImportDirective(node) => {
+                children.extend(node.symbol_aliases.iter().map(|alias| NodeRef::Identifier(&alias.foreign)));
+            }
+            NodeRef::UsingForDirective(node) => {
+                children.extend(node.library_name.iter().map(NodeRef::from));
+                children.extend(node.function.iter().map(NodeRef::from));
+                children.extend(node.function_list.iter().flatten().map(|function| NodeRef::IdentifierPath(&function.function)));
+                children.extend(node.type_name.iter().map(NodeRef::from));
+            }
+            NodeRef::ContractDefinition(node) => {
+                children.extend(node.documentation.iter().map(NodeRef::from));
+                children.extend(node.base_contracts.iter().map(NodeRef::from));
+                for child in &node.nodes {
+                    children.push(match child {
+                        ContractDefinitionChildNodes::FunctionDefinition(child) => NodeRef::FunctionDefinition(child),
+                        ContractDefinitionChildNodes::ModifierDefinition(child) => NodeRef::ModifierDefinition(child),
+                        ContractDefinitionChildNodes::StructDefinition(child) => NodeRef::StructDefinition(child),
+                        ContractDefinitionChildNodes::UserDefinedValueTypeDefinition(child) => NodeRef::UserDefinedValueTypeDefinition(child),
+                        ContractDefinitionChildNodes::VariableDeclaration(child) => NodeRef::VariableDeclaration(child),
+                        ContractDefinitionChildNodes::EnumDefinition(child) => NodeRef::EnumDefinition(child),
+                        ContractDefinitionChildNodes::ErrorDefinition(child) => NodeRef::ErrorDefinition(child),
+                        ContractDefinitionChildNodes::EventDefinition(child) => NodeRef::EventDefinition(child),
+                        ContractDefinitionChildNodes::UsingForDirective(child) => NodeRef::UsingForDirective(child),
+                    });
+                }
+            }
+            NodeRef::InheritanceSpecifier(node) => {
+                children.push(match &node.base_name {
+                    BaseName::UserDefinedTypeName(base_name) => NodeRef::UserDefinedTypeName(base_name),
+                    BaseName::IdentifierPath(base_name) => NodeRef::IdentifierPath(base_name),
+                });
+                children.extend(node.arguments.iter().flatten().map(NodeRef::from));
+            }
+            NodeRef::FunctionDefinition(node) => {
+                children.extend(node.documentation.iter().map(NodeRef::from));
+                children.push(NodeRef::ParameterList(&node.parameters));
+                children.extend(node.modifiers.iter().map(NodeRef::from));
+                children.extend(node.overrides.iter().map(NodeRef::from));
+                children.push(NodeRef::ParameterList(&node.return_parameters));
+                children.extend(node.body.iter().map(NodeRef::from));
+            }
+            NodeRef::ModifierDefinition(node) => {
+                children.extend(node.documentation.iter().map(NodeRef::from));
+                children.push(NodeRef::ParameterList(&node.parameters));
+                children.extend(node.overrides.iter().map(NodeRef::from));
+                children.push(NodeRef::from(&node.body));
+            }
+            NodeRef::ModifierInvocation(node) => {
+                children.push(match &node.modifier_name {
+                    ModifierName::Identifier(name) => NodeRef::Identifier(name),
+                    ModifierName::IdentifierPath(name) => NodeRef::IdentifierPath(name),
+                });
+                children.extend(node.arguments.iter().map(NodeRef::from));
+            }
+            NodeRef::StructDefinition(node) => children.extend(node.members.iter().map(NodeRef::from)),
+            NodeRef::EnumDefinition(node) => children.extend(node.members.iter().map(NodeRef::from)),
+            NodeRef::ErrorDefinition(node) => {
+                children.extend(node.documentation.iter().map(NodeRef::from));
+                children.extend(node.parameters.iter().map(NodeRef::from));
+            }
+            NodeRef::EventDefinition(node) => {
+                children.extend(node.documentation.iter().map(NodeRef::from));
+                children.extend(node.parameters.iter().map(NodeRef::from));
+            }
+            NodeRef::UserDefinedValueTypeDefinition(node) => children.push(NodeRef::from(&node.underlying_type)),
+            NodeRef::VariableDeclaration(node) => {
+                children.extend(node.documentation.iter().map(NodeRef::from));
+                children.extend(node.type_name.iter().map(NodeRef::from));
+                children.extend(node.overrides.iter().map(NodeRef::from));
+                children.extend(node.value.iter().map(NodeRef::from));
+            }
+            NodeRef::ParameterList(node) => children.extend(node.parameters.iter().map(NodeRef::from)),
+            NodeRef::OverrideSpecifier(node) => match &node.overrides {
+                OverridesEnum::UserDefinedTypeName(overrides) => children.extend(overrides.iter().map(NodeRef::from)),
+                OverridesEnum::Identifier(overrides) => children.extend(overrides.iter().map(NodeRef::from)),
+            },
+            NodeRef::ArrayTypeName(node) => {
+                children.push(NodeRef::from(&node.base_type));
+                children.extend(node.length.iter().map(NodeRef::from));
+            }
+            NodeRef::FunctionTypeName(node) => {
+                children.push(NodeRef::ParameterList(&node.parameter_types));
+                children.push(NodeRef::ParameterList(&node.return_parameter_types));
+            }
+            NodeRef::Mapping(node) => {
+                children.push(NodeRef::from(&node.key_type));
+                children.push(NodeRef::from(&node.value_type));
+            }
+            NodeRef::UserDefinedTypeName(node) => children.extend(node.path_node.iter().map(NodeRef::from)),
+            NodeRef::Block(node) => children.extend(node.statements.iter().flatten().map(NodeRef::from)),
+            NodeRef::DoWhileStatement(node) => {
+                children.push(NodeRef::from(&node.body));
+                children.push(NodeRef::from(&node.condition));
+            }
+            NodeRef::EmitStatement(node) => children.push(NodeRef::FunctionCall(&node.event_call)),
+            NodeRef::ExpressionStatement(node) => children.push(NodeRef::from(&node.expression)),
+            NodeRef::ForStatement(node) => {
+                match &node.initialization_expression {
+                    Some(InitializationExpression::ExpressionStatement(init)) => children.push(NodeRef::ExpressionStatement(init)),
+                    Some(InitializationExpression::VariableDeclarationStatement(init)) => children.push(NodeRef::VariableDeclarationStatement(init)),
+                    None => {}
+                }
+                children.extend(node.condition.iter().map(NodeRef::from));
+                children.extend(node.loop_expression.iter().map(NodeRef::from));
+                children.push(NodeRef::from(&node.body));
+            }
+            NodeRef::IfStatement(node) => {
+                children.push(NodeRef::from(&node.condition));
+                children.push(NodeRef::from(&node.true_body));
+                children.extend(node.false_body.iter().map(NodeRef::from));
+            }
+            NodeRef::Return(node) => children.extend(node.expression.iter().map(NodeRef::from)),
+            NodeRef::RevertStatement(node) => children.push(NodeRef::from(&node.error_call)),
+            NodeRef::TryStatement(node) => {
+                children.push(NodeRef::from(&node.external_call));
+                children.extend(node.clauses.iter().map(NodeRef::from));
+            }
+            NodeRef::TryCatchClause(node) => {
+                children.extend(node.parameters.iter().map(NodeRef::from));
+                children.push(NodeRef::Block(&node.block));
+            }
+            NodeRef::UncheckedBlock(node) => children.extend(node.statements.iter().map(NodeRef::from)),
+            NodeRef::VariableDeclarationStatement(node) => {
+                children.extend(node.declarations.iter().flatten().map(NodeRef::from));
+                children.extend(node.initial_value.iter().map(NodeRef::from));
+            }
+            NodeRef::WhileStatement(node) => {
+                children.push(NodeRef::from(&node.condition));
+                children.push(NodeRef::from(&node.body));
+            }
+            NodeRef::Assignment(node) => {
+                children.push(NodeRef::from(&node.left_hand_side));
+                children.push(NodeRef::from(&node.right_hand_side));
+            }
+            NodeRef::BinaryOperation(node) => {
+                children.push(NodeRef::from(&node.left_expression));
+                children.push(NodeRef::from(&node.right_expression));
+            }
+            NodeRef::Conditional(node) => {
+                children.push(NodeRef::from(&node.condition));
+                children.push(NodeRef::from(&node.true_expression));
+                children.push(NodeRef::from(&node.false_expression));
+            }
+            NodeRef::ElementaryTypeNameExpression(node) => children.push(NodeRef::ElementaryTypeName(&node.type_name)),
+            NodeRef::FunctionCall(node) => {
+                children.push(NodeRef::from(&node.expression));
+                children.extend(node.arguments.iter().map(NodeRef::from));
+            }
+            NodeRef::FunctionCallOptions(node) => {
+                children.push(NodeRef::from(&node.expression));
+                children.extend(node.options.iter().map(NodeRef::from));
+            }
+            NodeRef::IndexAccess(node) => {
+                children.push(NodeRef::from(&node.base_expression));
+                children.extend(node.index_expression.iter().map(NodeRef::from));
+            }
+            NodeRef::IndexRangeAccess(node) => {
+                children.push(NodeRef::from(&node.base_expression));
+                children.extend(node.start_expression.iter().map(NodeRef::from));
+                children.extend(node.end_expression.iter().map(NodeRef::from));
+            }
+            NodeRef::MemberAccess(node) => children.push(NodeRef::from(&node.expression)),
+            NodeRef::NewExpression(node) => children.push(NodeRef::from(&node.type_name)),
+            NodeRef::TupleExpression(node) => children.extend(node.components.iter().map(NodeRef::from)),
+            NodeRef::UnaryOperation(node) => children.push(NodeRef::from(&node.sub_expression)),
+            NodeRef::PragmaDirective(_)
+            | NodeRef::EnumValue(_)
+            | NodeRef::StructuredDocumentation(_)
+            | NodeRef::ElementaryTypeName(_)
+            | NodeRef::IdentifierPath(_)
+            | NodeRef::Break(_)
+            | NodeRef::Continue(_)
+            | NodeRef::InlineAssembly(_)
+            | NodeRef::PlaceholderStatement(_)
+            | NodeRef::Identifier(_)
+            | NodeRef::Literal(_) => {}
+        }
+        children
+    }
+}
+
+/// Maps the `id` of every node of one or several source units to the node and its parent.
+/// Node ids are only unique within a solc compilation, so the source units of an index must come from the same one.
+#[derive(Debug, Default)]
+pub struct AstIndex<'a> {
+    nodes: HashMap<usize, NodeRef<'a>>,
+    parents: HashMap<usize, usize>,
+}
+
+impl<'a> AstIndex<'a> {
+    pub fn new(source_unit: &'a SourceUnit) -> Self {
+        let mut index = AstIndex::default();
+        index.add_source_unit(source_unit);
+        index
+    }
+
+    pub fn add_source_unit(&mut self, source_unit: &'a SourceUnit) {
+        let mut stack: Vec<(NodeRef<'a>, Option<usize>)> = vec![(NodeRef::SourceUnit(source_unit), None)];
+
+        while let Some((node, parent)) = stack.pop() {
+            let id = node.id();
+            self.nodes.insert(id, node);
+            if let Some(parent) = parent {
+                self.parents.insert(id, parent);
+            }
+            stack.extend(node.children().into_iter().map(|child| (child, Some(id))));
+        }
+    }
+
+    pub fn get(&self, id: usize) -> Option<NodeRef<'a>> {
+        self.nodes.get(&id).copied()
+    }
+
+    pub fn parent(&self, id: usize) -> Option<NodeRef<'a>> {
+        self.parents.get(&id).and_then(|parent| self.get(*parent))
+    }
+
+    /// The ancestors of a node, from its parent to its source unit.
+    pub fn ancestors(&self, id: usize) -> impl Iterator<Item = NodeRef<'a>> + '_ {
+        std::iter::successors(self.parent(id), move |node| self.parent(node.id()))
+    }
+
+    /// The contract, interface or library containing a node.
+    pub fn enclosing_contract(&self, id: usize) -> Option<&'a ContractDefinition> {
+        self.ancestors(id).find_map(|node| match node {
+            NodeRef::ContractDefinition(contract) => Some(contract),
+            _ => None,
+        })
+    }
+
+    /// The function containing a node.
+    pub fn enclosing_function(&self, id: usize) -> Option<&'a FunctionDefinition> {
+        self.ancestors(id).find_map(|node| match node {
+            NodeRef::FunctionDefinition(function) => Some(function),
+            _ => None,
+        })
+    }
+
+    /// The declaration referenced by a node (identifier, member access, type name...), if it is in the index.
+    pub fn declaration(&self, node: NodeRef<'a>) -> Option<NodeRef<'a>> {
+        self.get(node.referenced_declaration()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use serde_json::{json, Value};
+    use super::*;
+
+    fn fixture(name: &str) -> Value {
+        let content = fs::read_to_string(format!("../solc-wrapper/tests/files/ast/{}.json", name)).expect("Could not find test data file");
+        serde_json::from_str(&content).unwrap()
+    }
+
+    // A contract with a function `mint` calling `require(_isMintAllowed, "...")`, where `_isMintAllowed` references `mint`
+    fn source_unit() -> SourceUnit {
+        let mut call = fixture("FunctionCall");
+        call["arguments"][0]["referencedDeclaration"] = json!(193);
+        let mut function = fixture("FunctionDefinition");
+        function["body"]["statements"] = json!([
+            { "id": 1000, "nodeType": "ExpressionStatement", "src": "1850:45:0", "expression": call },
+            fixture("ExpressionStatement"),
+        ]);
+        let mut contract = fixture("ContractDefinition");
+        contract["nodes"] = json!([function]);
+        let mut source_unit = fixture("SourceUnit");
+        source_unit["nodes"] = json!([fixture("PragmaDirective"), contract]);
+
+        serde_json::from_value(source_unit).unwrap()
+    }
+
+    #[test]
+    fn test_ast_index() {
+        let source_unit = source_unit();
+        let index = AstIndex::new(&source_unit);
+        let ancestors: Vec<usize> = index.ancestors(75).map(|node| node.id()).collect();
+
+        assert!(matches!(index.get(75), Some(NodeRef::Identifier(identifier)) if identifier.name == "require"));
+        assert!(matches!(index.parent(75), Some(NodeRef::FunctionCall(_))));
+        assert_eq!(ancestors, vec![78, 1000, 192, 193, 427, 428]);
+        assert_eq!(index.enclosing_function(151).map(|function| function.name.as_str()), Some("mint"));
+        assert_eq!(index.enclosing_contract(151).map(|contract| contract.id), Some(427));
+        assert_eq!(index.enclosing_function(427), None);
+    }
+
+    #[test]
+    fn test_ast_index_declaration() {
+        let source_unit = source_unit();
+        let index = AstIndex::new(&source_unit);
+        let identifier = index.get(76).unwrap();
+
+        assert_eq!(identifier.referenced_declaration(), Some(193));
+        assert!(matches!(index.declaration(identifier), Some(NodeRef::FunctionDefinition(function)) if function.name == "mint"));
+        assert_eq!(index.declaration(index.get(75).unwrap()), None);
+    }
+}
@@ -1,4 +1,7 @@
 pub mod parse;
+#[allow(clippy::module_inception)]
 pub mod ast;
 pub mod error;
-pub mod utils;
\ No newline at end of file
+pub mod utils;
+pub mod visitor;
+pub mod index;
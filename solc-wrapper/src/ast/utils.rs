@@ -1,4 +1,3 @@
-use std::ops::Deref;
 use crate::ast::ast::*;
 
 pub enum Nodes {
@@ -288,8 +287,8 @@ fn check_index_access_node(node: Box<IndexAccess>, nodes: &mut Vec<Nodes>, node_
         nodes.push(Nodes::IndexAccess(node.clone()));
     }
     check_expression_node(node.base_expression, nodes, node_type.clone());
-    if node.index_expression.is_some() {
-        check_expression_node(node.index_expression.unwrap(), nodes, node_type);
+    if let Some(index_expression) = node.index_expression {
+        check_expression_node(index_expression, nodes, node_type);
     }
 }
 
@@ -298,11 +297,11 @@ fn check_index_range_access_node(node: Box<IndexRangeAccess>, nodes: &mut Vec<No
         nodes.push(Nodes::IndexRangeAccess(node.clone()));
     }
     check_expression_node(node.base_expression, nodes, node_type.clone());
-    if node.start_expression.is_some() {
-        check_expression_node(node.start_expression.unwrap(), nodes, node_type.clone());
+    if let Some(start_expression) = node.start_expression {
+        check_expression_node(start_expression, nodes, node_type.clone());
     }
-    if node.end_expression.is_some() {
-        check_expression_node(node.end_expression.unwrap(), nodes, node_type);
+    if let Some(end_expression) = node.end_expression {
+        check_expression_node(end_expression, nodes, node_type);
     }
 }
 
@@ -345,8 +344,8 @@ fn check_block_node(node: Box<Block>, nodes: &mut Vec<Nodes>, node_type: NodeTyp
     if node_type == NodeType::Block {
         nodes.push(Nodes::Block(node.clone()));
     }
-    if node.statements.is_some() {
-        for statement in node.statements.unwrap() {
+    if let Some(statements) = node.statements {
+        for statement in statements {
             check_statement_node(statement, nodes, node_type.clone());
         }
     }
@@ -391,14 +390,13 @@ fn check_for_statement_node(node: Box<ForStatement>, nodes: &mut Vec<Nodes>, nod
         nodes.push(Nodes::ForStatement(node.clone()));
     }
     check_body_node(node.body, nodes, node_type.clone());
-    if node.condition.is_some() {
-        check_expression_node(node.condition.unwrap(), nodes, node_type.clone());
+    if let Some(condition) = node.condition {
+        check_expression_node(condition, nodes, node_type.clone());
     }
-    if node.loop_expression.is_some() {
-        check_expression_statement_node(Box::new(node.loop_expression.unwrap()), nodes, node_type.clone());
+    if let Some(loop_expression) = node.loop_expression {
+        check_expression_statement_node(Box::new(loop_expression), nodes, node_type.clone());
     }
-    if node.initialization_expression.is_some() {
-        let initialization_expression = node.initialization_expression.unwrap();
+    if let Some(initialization_expression) = node.initialization_expression {
         match initialization_expression {
             InitializationExpression::VariableDeclarationStatement(variable_declaration_statement) => {
                 check_variable_declaration_statement_node(Box::new(variable_declaration_statement), nodes, node_type.clone());
@@ -416,8 +414,8 @@ fn check_if_statement_node(node: Box<IfStatement>, nodes: &mut Vec<Nodes>, node_
     }
     check_expression_node(node.condition, nodes, node_type.clone());
     check_body_node(node.true_body, nodes, node_type.clone());
-    if node.false_body.is_some() {
-        check_body_node(node.false_body.unwrap(), nodes, node_type);
+    if let Some(false_body) = node.false_body {
+        check_body_node(false_body, nodes, node_type);
     }
 }
 
@@ -431,8 +429,8 @@ fn check_return_node(node: Box<Return>, nodes: &mut Vec<Nodes>, node_type: NodeT
     if node_type == NodeType::Return {
         nodes.push(Nodes::Return(node.clone()));
     }
-    if node.expression.is_some() {
-        check_expression_node(node.expression.unwrap(), nodes, node_type);
+    if let Some(expression) = node.expression {
+        check_expression_node(expression, nodes, node_type);
     }
 }
 
@@ -473,10 +471,8 @@ fn check_variable_declaration_statement_node(node: Box<VariableDeclarationStatem
     if node_type == NodeType::VariableDeclarationStatement {
         nodes.push(Nodes::VariableDeclarationStatement(node.clone()));
     }
-    for variable_declaration in node.declarations {
-        if variable_declaration.is_some() {
-            check_variable_declaration_node(Box::new(variable_declaration.unwrap()), nodes, node_type.clone());
-        }
+    for variable_declaration in node.declarations.into_iter().flatten() {
+        check_variable_declaration_node(Box::new(variable_declaration), nodes, node_type.clone());
     }
 }
 
@@ -492,8 +488,8 @@ fn check_modifier_invocation_node(node: Box<ModifierInvocation>, nodes: &mut Vec
     if node_type == NodeType::ModifierInvocation {
         nodes.push(Nodes::ModifierInvocation(node.clone()));
     }
-    if node.arguments.is_some() {
-        check_expression_node(node.arguments.unwrap(), nodes, node_type.clone());
+    if let Some(arguments) = node.arguments {
+        check_expression_node(arguments, nodes, node_type.clone());
     }
     match node.modifier_name {
         ModifierName::Identifier(identifier) => {
@@ -509,14 +505,14 @@ fn check_function_definition_node(node: Box<FunctionDefinition>, nodes: &mut Vec
     if node_type == NodeType::FunctionDefinition {
         nodes.push(Nodes::FunctionDefinition(node.clone()));
     }
-    if node.body.is_some() {
-        check_block_node(Box::new(node.body.unwrap()), nodes, node_type.clone());
+    if let Some(body) = node.body {
+        check_block_node(Box::new(body), nodes, node_type.clone());
     }
     for modifier in node.modifiers {
         check_modifier_invocation_node(Box::new(modifier), nodes, node_type.clone());
     }
-    if node.overrides.is_some() {
-        check_override_specifier_node(Box::new(node.overrides.unwrap()), nodes, node_type.clone());
+    if let Some(overrides) = node.overrides {
+        check_override_specifier_node(Box::new(overrides), nodes, node_type.clone());
     }
     check_parameter_list_node(Box::new(node.parameters), nodes, node_type.clone());
     check_parameter_list_node(Box::new(node.return_parameters), nodes, node_type);
@@ -545,8 +541,8 @@ fn check_modifier_definition_node(node: Box<ModifierDefinition>, nodes: &mut Vec
         nodes.push(Nodes::ModifierDefinition(node.clone()));
     }
     check_statement_node(node.body, nodes, node_type.clone());
-    if node.overrides.is_some() {
-        check_override_specifier_node(Box::new(node.overrides.unwrap()), nodes, node_type.clone());
+    if let Some(overrides) = node.overrides {
+        check_override_specifier_node(Box::new(overrides), nodes, node_type.clone());
     }
     check_parameter_list_node(Box::new(node.parameters), nodes, node_type);
 }
@@ -571,11 +567,11 @@ fn check_variable_declaration_node(node: Box<VariableDeclaration>, nodes: &mut V
     if node_type == NodeType::VariableDeclaration {
         nodes.push(Nodes::VariableDeclaration(node.clone()));
     }
-    if node.value.is_some() {
-        check_expression_node(node.value.unwrap(), nodes, node_type.clone());
+    if let Some(value) = node.value {
+        check_expression_node(value, nodes, node_type.clone());
     }
-    if node.type_name.is_some() {
-        check_typename_node(node.type_name.unwrap(), nodes, node_type);
+    if let Some(type_name) = node.type_name {
+        check_typename_node(type_name, nodes, node_type);
     }
 }
 
@@ -598,8 +594,8 @@ fn check_error_definition_node(node: Box<ErrorDefinition>, nodes: &mut Vec<Nodes
     if node_type == NodeType::ErrorDefinition {
         nodes.push(Nodes::ErrorDefinition(node.clone()));
     }
-    if node.parameters.is_some() {
-        check_parameter_list_node(Box::new(node.parameters.unwrap()), nodes, node_type);
+    if let Some(parameters) = node.parameters {
+        check_parameter_list_node(Box::new(parameters), nodes, node_type);
     }
 }
 
@@ -607,8 +603,8 @@ fn check_event_definition_node(node: Box<EventDefinition>, nodes: &mut Vec<Nodes
     if node_type == NodeType::EventDefinition {
         nodes.push(Nodes::EventDefinition(node.clone()));
     }
-    if node.parameters.is_some() {
-        check_parameter_list_node(Box::new(node.parameters.unwrap()), nodes, node_type);
+    if let Some(parameters) = node.parameters {
+        check_parameter_list_node(Box::new(parameters), nodes, node_type);
     }
 }
 
@@ -616,20 +612,19 @@ fn check_using_for_directive_node(node: Box<UsingForDirective>, nodes: &mut Vec<
     if node_type == NodeType::UsingForDirective {
         nodes.push(Nodes::UsingForDirective(node.clone()));
     }
-    if node.function_list.is_some() {
-        let function_list = node.function_list.unwrap();
+    if let Some(function_list) = node.function_list {
         for function in function_list {
             check_identifier_path_node(Box::new(function.function), nodes, node_type.clone());
         }
     }
-    if node.function.is_some() {
-        check_identifier_path_node(Box::new(node.function.unwrap()), nodes, node_type.clone());
+    if let Some(function) = node.function {
+        check_identifier_path_node(Box::new(function), nodes, node_type.clone());
     }
-    if node.library_name.is_some() {
-        check_expression_node(node.library_name.unwrap(), nodes, node_type.clone());
+    if let Some(library_name) = node.library_name {
+        check_expression_node(library_name, nodes, node_type.clone());
     }
-    if node.type_name.is_some() {
-        check_typename_node(node.type_name.unwrap(), nodes, node_type);
+    if let Some(type_name) = node.type_name {
+        check_typename_node(type_name, nodes, node_type);
     }
 }
 
@@ -637,8 +632,8 @@ fn check_inheritance_specifier_node(node: Box<InheritanceSpecifier>, nodes: &mut
     if node_type == NodeType::InheritanceSpecifier {
         nodes.push(Nodes::InheritanceSpecifier(node.clone()));
     }
-    if node.arguments.is_some() {
-        for argument in node.arguments.unwrap() {
+    if let Some(arguments) = node.arguments {
+        for argument in arguments {
             check_expression_node(argument, nodes, node_type.clone());
         }
     }
@@ -652,12 +647,12 @@ fn check_contract_definition_node(node: Box<ContractDefinition>, nodes: &mut Vec
         check_inheritance_specifier_node(Box::new(base), nodes, node_type.clone());
     }
     for node in node.nodes {
-        check_contract_definition_child_node(Box::new(node), nodes, node_type.clone());
+        check_contract_definition_child_node(node, nodes, node_type.clone());
     }
 }
 
-fn check_contract_definition_child_node(node: Box<ContractDefinitionChildNodes>, nodes: &mut Vec<Nodes>, node_type: NodeType) {
-    match *node {
+fn check_contract_definition_child_node(node: ContractDefinitionChildNodes, nodes: &mut Vec<Nodes>, node_type: NodeType) {
+    match node {
         ContractDefinitionChildNodes::UsingForDirective(node) => check_using_for_directive_node(node, nodes, node_type),
         ContractDefinitionChildNodes::StructDefinition(node) => check_struct_definition_node(node, nodes, node_type),
         ContractDefinitionChildNodes::EnumDefinition(node) => check_enum_definition_node(node, nodes, node_type),
@@ -675,8 +670,8 @@ fn check_user_defined_type_name_node(node: Box<UserDefinedTypeName>, nodes: &mut
     if node_type == NodeType::UserDefinedTypeName {
         nodes.push(Nodes::UserDefinedTypeName(node.clone()));
     }
-    if node.path_node.is_some() {
-        check_identifier_path_node(Box::new(node.path_node.unwrap()), nodes, node_type);
+    if let Some(path_node) = node.path_node {
+        check_identifier_path_node(Box::new(path_node), nodes, node_type);
     }
 }
 
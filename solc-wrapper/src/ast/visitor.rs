@@ -0,0 +1,631 @@
+//! Traversal of the AST by reference.
+//!
+//! A [`Visitor`] gets a `visit_*` call for every node of the tree, parents before their children, when the tree is
+//! walked with [`walk_source_unit`](visit::walk_source_unit) (or any other `walk_*` function for a subtree).
+//! Hooks do nothing by default, so visitors only implement the ones they need, and several visitors can be run in a
+//! single walk as a slice: `walk_source_unit(&mut visitors[..], &source_unit)`.
+//! [`VisitorMut`] is the same with mutable references, to edit the tree in place.
+
+macro_rules! make_visitor {
+    (@trait $visitor:ident, $($hook:ident: $node:ident),*) => {
+        pub trait $visitor {
+            $(
+                fn $hook(&mut self, _node: node_ref!($node)) {}
+            )*
+        }
+
+        impl<T: $visitor + ?Sized> $visitor for Box<T> {
+            $(
+                fn $hook(&mut self, node: node_ref!($node)) {
+                    (**self).$hook(node)
+                }
+            )*
+        }
+
+        // Runs every visitor of the slice on each node, to share a single walk of the tree
+        impl<T: $visitor> $visitor for [T] {
+            $(
+                fn $hook(&mut self, node: node_ref!($node)) {
+                    for visitor in self.iter_mut() {
+                        visitor.$hook(node_ref!(*node));
+                    }
+                }
+            )*
+        }
+    };
+
+    ($module:ident, $visitor:ident, [$($mutability:tt)?]) => {
+        pub mod $module {
+            use crate::ast::ast::*;
+
+            // The type of a node reference, or a reborrow of a reference to a node
+            macro_rules! node_ref {
+                (*$reference:ident) => { &$($mutability)? *$reference };
+                ($reference:ident) => { &$($mutability)? $reference };
+            }
+
+            make_visitor!(@trait $visitor,
+                visit_source_unit: SourceUnit,
+                visit_pragma_directive: PragmaDirective,
+                visit_import_directive: ImportDirective,
+                visit_using_for_directive: UsingForDirective,
+                visit_contract_definition: ContractDefinition,
+                visit_inheritance_specifier: InheritanceSpecifier,
+                visit_function_definition: FunctionDefinition,
+                visit_modifier_definition: ModifierDefinition,
+                visit_modifier_invocation: ModifierInvocation,
+                visit_struct_definition: StructDefinition,
+                visit_enum_definition: EnumDefinition,
+                visit_enum_value: EnumValue,
+                visit_error_definition: ErrorDefinition,
+                visit_event_definition: EventDefinition,
+                visit_user_defined_value_type_definition: UserDefinedValueTypeDefinition,
+                visit_variable_declaration: VariableDeclaration,
+                visit_parameter_list: ParameterList,
+                visit_override_specifier: OverrideSpecifier,
+                visit_structured_documentation: StructuredDocumentation,
+                visit_type_name: TypeName,
+                visit_array_type_name: ArrayTypeName,
+                visit_elementary_type_name: ElementaryTypeName,
+                visit_function_type_name: FunctionTypeName,
+                visit_mapping: Mapping,
+                visit_user_defined_type_name: UserDefinedTypeName,
+                visit_identifier_path: IdentifierPath,
+                visit_statement: Statement,
+                visit_block: Block,
+                visit_break: Break,
+                visit_continue: Continue,
+                visit_do_while_statement: DoWhileStatement,
+                visit_emit_statement: EmitStatement,
+                visit_expression_statement: ExpressionStatement,
+                visit_for_statement: ForStatement,
+                visit_if_statement: IfStatement,
+                visit_inline_assembly: InlineAssembly,
+                visit_placeholder_statement: PlaceholderStatement,
+                visit_return: Return,
+                visit_revert_statement: RevertStatement,
+                visit_try_statement: TryStatement,
+                visit_try_catch_clause: TryCatchClause,
+                visit_unchecked_block: UncheckedBlock,
+                visit_variable_declaration_statement: VariableDeclarationStatement,
+                visit_while_statement: WhileStatement,
+                visit_expression: Expression,
+                visit_assignment: Assignment,
+                visit_binary_operation: BinaryOperation,
+                visit_conditional: Conditional,
+                visit_elementary_type_name_expression: ElementaryTypeNameExpression,
+                visit_function_call: FunctionCall,
+                visit_function_call_options: FunctionCallOptions,
+                visit_identifier: Identifier,
+                visit_index_access: IndexAccess,
+                visit_index_range_access: IndexRangeAccess,
+                visit_literal: Literal,
+                visit_member_access: MemberAccess,
+                visit_new_expression: NewExpression,
+                visit_tuple_expression: TupleExpression,
+                visit_unary_operation: UnaryOperation
+            );
+
+            pub fn walk_source_unit<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? SourceUnit) {
+                visitor.visit_source_unit(node);
+                for child in &$($mutability)? node.nodes {
+                    match child {
+                        SourceUnitChildNodes::ContractDefinition(child) => walk_contract_definition(visitor, child),
+                        SourceUnitChildNodes::StructDefinition(child) => walk_struct_definition(visitor, child),
+                        SourceUnitChildNodes::EnumDefinition(child) => walk_enum_definition(visitor, child),
+                        SourceUnitChildNodes::ErrorDefinition(child) => walk_error_definition(visitor, child),
+                        SourceUnitChildNodes::PragmaDirective(child) => visitor.visit_pragma_directive(child),
+                        SourceUnitChildNodes::ImportDirective(child) => walk_import_directive(visitor, child),
+                        SourceUnitChildNodes::UsingForDirective(child) => walk_using_for_directive(visitor, child),
+                        SourceUnitChildNodes::Other(_) => {}
+                    }
+                }
+            }
+
+            pub fn walk_import_directive<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? ImportDirective) {
+                visitor.visit_import_directive(node);
+                for alias in &$($mutability)? node.symbol_aliases {
+                    visitor.visit_identifier(&$($mutability)? alias.foreign);
+                }
+            }
+
+            pub fn walk_using_for_directive<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? UsingForDirective) {
+                visitor.visit_using_for_directive(node);
+                if let Some(library_name) = &$($mutability)? node.library_name {
+                    walk_expression(visitor, library_name);
+                }
+                if let Some(function) = &$($mutability)? node.function {
+                    visitor.visit_identifier_path(function);
+                }
+                if let Some(function_list) = &$($mutability)? node.function_list {
+                    for function in function_list {
+                        visitor.visit_identifier_path(&$($mutability)? function.function);
+                    }
+                }
+                if let Some(type_name) = &$($mutability)? node.type_name {
+                    walk_type_name(visitor, type_name);
+                }
+            }
+
+            pub fn walk_contract_definition<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? ContractDefinition) {
+                visitor.visit_contract_definition(node);
+                if let Some(documentation) = &$($mutability)? node.documentation {
+                    visitor.visit_structured_documentation(documentation);
+                }
+                for base in &$($mutability)? node.base_contracts {
+                    walk_inheritance_specifier(visitor, base);
+                }
+                for child in &$($mutability)? node.nodes {
+                    match child {
+                        ContractDefinitionChildNodes::FunctionDefinition(child) => walk_function_definition(visitor, child),
+                        ContractDefinitionChildNodes::ModifierDefinition(child) => walk_modifier_definition(visitor, child),
+                        ContractDefinitionChildNodes::StructDefinition(child) => walk_struct_definition(visitor, child),
+                        ContractDefinitionChildNodes::UserDefinedValueTypeDefinition(child) => walk_user_defined_value_type_definition(visitor, child),
+                        ContractDefinitionChildNodes::VariableDeclaration(child) => walk_variable_declaration(visitor, child),
+                        ContractDefinitionChildNodes::EnumDefinition(child) => walk_enum_definition(visitor, child),
+                        ContractDefinitionChildNodes::ErrorDefinition(child) => walk_error_definition(visitor, child),
+                        ContractDefinitionChildNodes::EventDefinition(child) => walk_event_definition(visitor, child),
+                        ContractDefinitionChildNodes::UsingForDirective(child) => walk_using_for_directive(visitor, child),
+                    }
+                }
+            }
+
+            pub fn walk_inheritance_specifier<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? InheritanceSpecifier) {
+                visitor.visit_inheritance_specifier(node);
+                match &$($mutability)? node.base_name {
+                    BaseName::UserDefinedTypeName(base_name) => walk_user_defined_type_name(visitor, base_name),
+                    BaseName::IdentifierPath(base_name) => visitor.visit_identifier_path(base_name),
+                }
+                if let Some(arguments) = &$($mutability)? node.arguments {
+                    for argument in arguments {
+                        walk_expression(visitor, argument);
+                    }
+                }
+            }
+
+            pub fn walk_function_definition<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? FunctionDefinition) {
+                visitor.visit_function_definition(node);
+                if let Some(documentation) = &$($mutability)? node.documentation {
+                    visitor.visit_structured_documentation(documentation);
+                }
+                walk_parameter_list(visitor, &$($mutability)? node.parameters);
+                for modifier in &$($mutability)? node.modifiers {
+                    walk_modifier_invocation(visitor, modifier);
+                }
+                if let Some(overrides) = &$($mutability)? node.overrides {
+                    walk_override_specifier(visitor, overrides);
+                }
+                walk_parameter_list(visitor, &$($mutability)? node.return_parameters);
+                if let Some(body) = &$($mutability)? node.body {
+                    walk_block(visitor, body);
+                }
+            }
+
+            pub fn walk_modifier_definition<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? ModifierDefinition) {
+                visitor.visit_modifier_definition(node);
+                if let Some(documentation) = &$($mutability)? node.documentation {
+                    visitor.visit_structured_documentation(documentation);
+                }
+                walk_parameter_list(visitor, &$($mutability)? node.parameters);
+                if let Some(overrides) = &$($mutability)? node.overrides {
+                    walk_override_specifier(visitor, overrides);
+                }
+                walk_statement(visitor, &$($mutability)? node.body);
+            }
+
+            pub fn walk_modifier_invocation<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? ModifierInvocation) {
+                visitor.visit_modifier_invocation(node);
+                match &$($mutability)? node.modifier_name {
+                    ModifierName::Identifier(name) => visitor.visit_identifier(name),
+                    ModifierName::IdentifierPath(name) => visitor.visit_identifier_path(name),
+                }
+                if let Some(arguments) = &$($mutability)? node.arguments {
+                    walk_expression(visitor, arguments);
+                }
+            }
+
+            pub fn walk_struct_definition<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? StructDefinition) {
+                visitor.visit_struct_definition(node);
+                for member in &$($mutability)? node.members {
+                    walk_variable_declaration(visitor, member);
+                }
+            }
+
+            pub fn walk_enum_definition<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? EnumDefinition) {
+                visitor.visit_enum_definition(node);
+                for member in &$($mutability)? node.members {
+                    visitor.visit_enum_value(member);
+                }
+            }
+
+            pub fn walk_error_definition<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? ErrorDefinition) {
+                visitor.visit_error_definition(node);
+                if let Some(documentation) = &$($mutability)? node.documentation {
+                    visitor.visit_structured_documentation(documentation);
+                }
+                if let Some(parameters) = &$($mutability)? node.parameters {
+                    walk_parameter_list(visitor, parameters);
+                }
+            }
+
+            pub fn walk_event_definition<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? EventDefinition) {
+                visitor.visit_event_definition(node);
+                if let Some(documentation) = &$($mutability)? node.documentation {
+                    visitor.visit_structured_documentation(documentation);
+                }
+                if let Some(parameters) = &$($mutability)? node.parameters {
+                    walk_parameter_list(visitor, parameters);
+                }
+            }
+
+            pub fn walk_user_defined_value_type_definition<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? UserDefinedValueTypeDefinition) {
+                visitor.visit_user_defined_value_type_definition(node);
+                walk_type_name(visitor, &$($mutability)? node.underlying_type);
+            }
+
+            pub fn walk_variable_declaration<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? VariableDeclaration) {
+                visitor.visit_variable_declaration(node);
+                if let Some(documentation) = &$($mutability)? node.documentation {
+                    visitor.visit_structured_documentation(documentation);
+                }
+                if let Some(type_name) = &$($mutability)? node.type_name {
+                    walk_type_name(visitor, type_name);
+                }
+                if let Some(overrides) = &$($mutability)? node.overrides {
+                    walk_override_specifier(visitor, overrides);
+                }
+                if let Some(value) = &$($mutability)? node.value {
+                    walk_expression(visitor, value);
+                }
+            }
+
+            pub fn walk_parameter_list<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? ParameterList) {
+                visitor.visit_parameter_list(node);
+                for parameter in &$($mutability)? node.parameters {
+                    walk_variable_declaration(visitor, parameter);
+                }
+            }
+
+            pub fn walk_override_specifier<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? OverrideSpecifier) {
+                visitor.visit_override_specifier(node);
+                match &$($mutability)? node.overrides {
+                    OverridesEnum::UserDefinedTypeName(overrides) => {
+                        for type_name in overrides {
+                            walk_user_defined_type_name(visitor, type_name);
+                        }
+                    }
+                    OverridesEnum::Identifier(overrides) => {
+                        for path in overrides {
+                            visitor.visit_identifier_path(path);
+                        }
+                    }
+                }
+            }
+
+            pub fn walk_type_name<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? TypeName) {
+                visitor.visit_type_name(node);
+                match node {
+                    TypeName::ArrayTypeName(node) => {
+                        visitor.visit_array_type_name(node);
+                        walk_type_name(visitor, &$($mutability)? node.base_type);
+                        if let Some(length) = &$($mutability)? node.length {
+                            walk_expression(visitor, length);
+                        }
+                    }
+                    TypeName::ElementaryTypeName(node) => visitor.visit_elementary_type_name(node),
+                    TypeName::FunctionTypeName(node) => {
+                        visitor.visit_function_type_name(node);
+                        walk_parameter_list(visitor, &$($mutability)? node.parameter_types);
+                        walk_parameter_list(visitor, &$($mutability)? node.return_parameter_types);
+                    }
+                    TypeName::Mapping(node) => {
+                        visitor.visit_mapping(node);
+                        walk_type_name(visitor, &$($mutability)? node.key_type);
+                        walk_type_name(visitor, &$($mutability)? node.value_type);
+                    }
+                    TypeName::UserDefinedTypeName(node) => walk_user_defined_type_name(visitor, node),
+                }
+            }
+
+            pub fn walk_user_defined_type_name<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? UserDefinedTypeName) {
+                visitor.visit_user_defined_type_name(node);
+                if let Some(path) = &$($mutability)? node.path_node {
+                    visitor.visit_identifier_path(path);
+                }
+            }
+
+            fn walk_body<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? Body) {
+                match node {
+                    Body::Block(block) => walk_block(visitor, block),
+                    Body::Statement(statement) => walk_statement(visitor, statement),
+                }
+            }
+
+            pub fn walk_block<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? Block) {
+                visitor.visit_block(node);
+                if let Some(statements) = &$($mutability)? node.statements {
+                    for statement in statements {
+                        walk_statement(visitor, statement);
+                    }
+                }
+            }
+
+            pub fn walk_statement<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? Statement) {
+                visitor.visit_statement(node);
+                match node {
+                    Statement::VariableDeclarationStatement(node) => walk_variable_declaration_statement(visitor, node),
+                    Statement::ForStatement(node) => {
+                        visitor.visit_for_statement(node);
+                        match &$($mutability)? node.initialization_expression {
+                            Some(InitializationExpression::ExpressionStatement(init)) => walk_expression_statement(visitor, init),
+                            Some(InitializationExpression::VariableDeclarationStatement(init)) => walk_variable_declaration_statement(visitor, init),
+                            None => {}
+                        }
+                        if let Some(condition) = &$($mutability)? node.condition {
+                            walk_expression(visitor, condition);
+                        }
+                        if let Some(loop_expression) = &$($mutability)? node.loop_expression {
+                            walk_expression_statement(visitor, loop_expression);
+                        }
+                        walk_body(visitor, &$($mutability)? node.body);
+                    }
+                    Statement::IfStatement(node) => {
+                        visitor.visit_if_statement(node);
+                        walk_expression(visitor, &$($mutability)? node.condition);
+                        walk_body(visitor, &$($mutability)? node.true_body);
+                        if let Some(false_body) = &$($mutability)? node.false_body {
+                            walk_body(visitor, false_body);
+                        }
+                    }
+                    Statement::DoWhileStatement(node) => {
+                        visitor.visit_do_while_statement(node);
+                        walk_body(visitor, &$($mutability)? node.body);
+                        walk_expression(visitor, &$($mutability)? node.condition);
+                    }
+                    Statement::Return(node) => {
+                        visitor.visit_return(node);
+                        if let Some(expression) = &$($mutability)? node.expression {
+                            walk_expression(visitor, expression);
+                        }
+                    }
+                    Statement::TryStatement(node) => {
+                        visitor.visit_try_statement(node);
+                        walk_expression(visitor, &$($mutability)? node.external_call);
+                        for clause in &$($mutability)? node.clauses {
+                            visitor.visit_try_catch_clause(clause);
+                            if let Some(parameters) = &$($mutability)? clause.parameters {
+                                walk_parameter_list(visitor, parameters);
+                            }
+                            walk_block(visitor, &$($mutability)? clause.block);
+                        }
+                    }
+                    Statement::WhileStatement(node) => {
+                        visitor.visit_while_statement(node);
+                        walk_expression(visitor, &$($mutability)? node.condition);
+                        walk_statement(visitor, &$($mutability)? node.body);
+                    }
+                    Statement::UncheckedBlock(node) => {
+                        visitor.visit_unchecked_block(node);
+                        for statement in &$($mutability)? node.statements {
+                            walk_statement(visitor, statement);
+                        }
+                    }
+                    Statement::EmitStatement(node) => {
+                        visitor.visit_emit_statement(node);
+                        walk_function_call(visitor, &$($mutability)? node.event_call);
+                    }
+                    Statement::RevertStatement(node) => {
+                        visitor.visit_revert_statement(node);
+                        walk_statement(visitor, &$($mutability)? node.error_call);
+                    }
+                    Statement::ExpressionStatement(node) => walk_expression_statement(visitor, node),
+                    Statement::Block(node) => walk_block(visitor, node),
+                    Statement::Continue(node) => visitor.visit_continue(node),
+                    Statement::Break(node) => visitor.visit_break(node),
+                    Statement::PlaceholderStatement(node) => visitor.visit_placeholder_statement(node),
+                }
+            }
+
+            pub fn walk_expression_statement<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? ExpressionStatement) {
+                visitor.visit_expression_statement(node);
+                walk_expression(visitor, &$($mutability)? node.expression);
+            }
+
+            pub fn walk_variable_declaration_statement<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? VariableDeclarationStatement) {
+                visitor.visit_variable_declaration_statement(node);
+                for declaration in (&$($mutability)? node.declarations).into_iter().flatten() {
+                    walk_variable_declaration(visitor, declaration);
+                }
+                if let Some(initial_value) = &$($mutability)? node.initial_value {
+                    walk_expression(visitor, initial_value);
+                }
+            }
+
+            pub fn walk_function_call<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? FunctionCall) {
+                visitor.visit_function_call(node);
+                walk_expression(visitor, &$($mutability)? node.expression);
+                for argument in &$($mutability)? node.arguments {
+                    walk_expression(visitor, argument);
+                }
+            }
+
+            pub fn walk_expression<V: $visitor + ?Sized>(visitor: &mut V, node: &$($mutability)? Expression) {
+                visitor.visit_expression(node);
+                match node {
+                    Expression::Assignment(node) => {
+                        visitor.visit_assignment(node);
+                        walk_expression(visitor, &$($mutability)? node.left_hand_side);
+                        walk_expression(visitor, &$($mutability)? node.right_hand_side);
+                    }
+                    Expression::BinaryOperation(node) => {
+                        visitor.visit_binary_operation(node);
+                        walk_expression(visitor, &$($mutability)? node.left_expression);
+                        walk_expression(visitor, &$($mutability)? node.right_expression);
+                    }
+                    Expression::Conditional(node) => {
+                        visitor.visit_conditional(node);
+                        walk_expression(visitor, &$($mutability)? node.condition);
+                        walk_expression(visitor, &$($mutability)? node.true_expression);
+                        walk_expression(visitor, &$($mutability)? node.false_expression);
+                    }
+                    Expression::ElementaryTypeNameExpression(node) => {
+                        visitor.visit_elementary_type_name_expression(node);
+                        visitor.visit_elementary_type_name(&$($mutability)? node.type_name);
+                    }
+                    Expression::FunctionCall(node) => walk_function_call(visitor, node),
+                    Expression::FunctionCallOptions(node) => {
+                        visitor.visit_function_call_options(node);
+                        walk_expression(visitor, &$($mutability)? node.expression);
+                        for option in &$($mutability)? node.options {
+                            walk_expression(visitor, option);
+                        }
+                    }
+                    Expression::Identifier(node) => visitor.visit_identifier(node),
+                    Expression::IdentifierPath(node) => visitor.visit_identifier_path(node),
+                    Expression::IndexAccess(node) => {
+                        visitor.visit_index_access(node);
+                        walk_expression(visitor, &$($mutability)? node.base_expression);
+                        if let Some(index) = &$($mutability)? node.index_expression {
+                            walk_expression(visitor, index);
+                        }
+                    }
+                    Expression::IndexRangeAccess(node) => {
+                        visitor.visit_index_range_access(node);
+                        walk_expression(visitor, &$($mutability)? node.base_expression);
+                        if let Some(start) = &$($mutability)? node.start_expression {
+                            walk_expression(visitor, start);
+                        }
+                        if let Some(end) = &$($mutability)? node.end_expression {
+                            walk_expression(visitor, end);
+                        }
+                    }
+                    Expression::Literal(node) => visitor.visit_literal(node),
+                    Expression::MemberAccess(node) => {
+                        visitor.visit_member_access(node);
+                        walk_expression(visitor, &$($mutability)? node.expression);
+                    }
+                    Expression::NewExpression(node) => {
+                        visitor.visit_new_expression(node);
+                        walk_type_name(visitor, &$($mutability)? node.type_name);
+                    }
+                    Expression::TupleExpression(node) => {
+                        visitor.visit_tuple_expression(node);
+                        for component in &$($mutability)? node.components {
+                            walk_expression(visitor, component);
+                        }
+                    }
+                    Expression::UnaryOperation(node) => {
+                        visitor.visit_unary_operation(node);
+                        walk_expression(visitor, &$($mutability)? node.sub_expression);
+                    }
+                }
+            }
+        }
+    };
+
+}
+
+make_visitor!(visit, Visitor, []);
+make_visitor!(visit_mut, VisitorMut, [mut]);
+
+pub use visit::Visitor;
+pub use visit_mut::VisitorMut;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+    use serde_json::{json, Value};
+    use crate::ast::ast::*;
+    use super::*;
+
+    fn fixture(name: &str) -> Value {
+        let content = fs::read_to_string(format!("../solc-wrapper/tests/files/ast/{}.json", name)).expect("Could not find test data file");
+        serde_json::from_str(&content).unwrap()
+    }
+
+    // A contract with a function calling `require(_isMintAllowed, "...")` then assigning a variable
+    fn source_unit() -> SourceUnit {
+        let mut function = fixture("FunctionDefinition");
+        function["body"]["statements"] = json!([
+            { "id": 1000, "nodeType": "ExpressionStatement", "src": "1850:45:0", "expression": fixture("FunctionCall") },
+            fixture("ExpressionStatement"),
+        ]);
+        let mut contract = fixture("ContractDefinition");
+        contract["nodes"] = json!([function]);
+        let mut source_unit = fixture("SourceUnit");
+        source_unit["nodes"] = json!([fixture("PragmaDirective"), contract]);
+
+        serde_json::from_value(source_unit).unwrap()
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        counts: HashMap<&'static str, usize>,
+    }
+
+    impl Counter {
+        fn count(&mut self, node_type: &'static str) {
+            *self.counts.entry(node_type).or_default() += 1;
+        }
+    }
+
+    impl Visitor for Counter {
+        fn visit_contract_definition(&mut self, _node: &ContractDefinition) {
+            self.count("ContractDefinition");
+        }
+
+        fn visit_function_call(&mut self, _node: &FunctionCall) {
+            self.count("FunctionCall");
+        }
+
+        fn visit_identifier(&mut self, _node: &Identifier) {
+            self.count("Identifier");
+        }
+
+        fn visit_literal(&mut self, _node: &Literal) {
+            self.count("Literal");
+        }
+
+        fn visit_parameter_list(&mut self, _node: &ParameterList) {
+            self.count("ParameterList");
+        }
+    }
+
+    struct Renamer;
+
+    impl VisitorMut for Renamer {
+        fn visit_identifier(&mut self, node: &mut Identifier) {
+            node.name = node.name.to_uppercase();
+        }
+    }
+
+    #[test]
+    fn test_visitor() {
+        let source_unit = source_unit();
+        let mut visitors = vec![Counter::default(), Counter::default()];
+        visit::walk_source_unit(&mut visitors[..], &source_unit);
+
+        for visitor in visitors {
+            assert_eq!(visitor.counts["ContractDefinition"], 1);
+            assert_eq!(visitor.counts["FunctionCall"], 1);
+            assert_eq!(visitor.counts["Identifier"], 3);
+            assert_eq!(visitor.counts["Literal"], 2);
+            assert_eq!(visitor.counts["ParameterList"], 2);
+        }
+    }
+
+    #[test]
+    fn test_visitor_mut() {
+        let mut source_unit = source_unit();
+        visit_mut::walk_source_unit(&mut Renamer, &mut source_unit);
+
+        let mut counter = Counter::default();
+        visit::walk_source_unit(&mut counter, &source_unit);
+        let json = serde_json::to_string(&source_unit).unwrap();
+
+        assert_eq!(counter.counts["Identifier"], 3);
+        assert!(json.contains("\"REQUIRE\""));
+        assert!(!json.contains("\"require\""));
+    }
+}
@@ -0,0 +1,229 @@
+//! On-disk cache of the ASTs output by solc, so that unchanged sources skip the compiler on the next runs.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+use semver::Version;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use crate::ast::ast::SourceUnit;
+use crate::standard_json::CompilerError;
+use crate::ParsedSource;
+
+pub const DEFAULT_CACHE_DIR: &str = ".solidhunter/cache";
+pub const DEFAULT_MAX_SIZE: u64 = 256 * 1024 * 1024;
+// Part of every key, bumping it invalidates all the entries written by previous versions
+const CACHE_FORMAT: &str = "solidhunter-ast-cache-v2";
+
+// An entry, the diagnostics coming first so that the AST is written as output by solc
+#[derive(Deserialize)]
+struct CacheEntry {
+    diagnostics: Vec<CompilerError>,
+    ast: SourceUnit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// Directory of the entries, created when needed.
+    pub dir: PathBuf,
+    /// Total size of the entries in bytes, the least recently used ones are removed above it.
+    pub max_size: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            dir: PathBuf::from(DEFAULT_CACHE_DIR),
+            max_size: DEFAULT_MAX_SIZE,
+        }
+    }
+}
+
+/// The AST JSON of sources, stored by a hash of their content, of the solc version and of the compiler settings.
+/// Any change of them gives a new key: stale entries are never read again, and are removed once the cache is full.
+#[derive(Debug)]
+pub struct AstCache {
+    config: CacheConfig,
+    // Bytes written since the last pruning
+    written: AtomicU64,
+}
+
+impl AstCache {
+    pub fn new(config: CacheConfig) -> Self {
+        AstCache { config, written: AtomicU64::new(0) }
+    }
+
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    pub fn key(version: &Version, settings: &[&str], content: &str) -> String {
+        let mut hasher = Sha256::new();
+        for part in [CACHE_FORMAT, &version.to_string()].into_iter().chain(settings.iter().copied()).chain([content]) {
+            // Prefixed by their length so that parts can't run into each other
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part.as_bytes());
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.config.dir.join(format!("{}.json", key))
+    }
+
+    /// The AST and the diagnostics stored under `key`. Entries that can't be parsed anymore are removed.
+    pub fn get(&self, key: &str) -> Option<ParsedSource> {
+        let path = self.entry_path(key);
+        let json = std::fs::read_to_string(&path).ok()?;
+        match serde_json::from_str::<CacheEntry>(&json) {
+            Ok(entry) => {
+                // The modification time orders the entries for the eviction
+                let _ = std::fs::File::options().write(true).open(&path).and_then(|file| file.set_modified(SystemTime::now()));
+                Some(ParsedSource { ast: entry.ast, diagnostics: entry.diagnostics })
+            }
+            Err(_) => {
+                let _ = std::fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// Stores the AST `json` and the `diagnostics` of solc under `key`, then prunes the cache once a tenth of its size
+    /// has been written.
+    pub fn insert(&self, key: &str, json: &str, diagnostics: &[CompilerError]) -> io::Result<()> {
+        let json = format!(r#"{{"diagnostics":{},"ast":{}}}"#, serde_json::to_string(diagnostics)?, json);
+        std::fs::create_dir_all(&self.config.dir)?;
+        // Written aside then renamed, so that concurrent runs never read a partial entry
+        let tmp_path = self.config.dir.join(format!("{}.{}.tmp", key, std::process::id()));
+        std::fs::write(&tmp_path, &json)?;
+        std::fs::rename(&tmp_path, self.entry_path(key))?;
+
+        let written = self.written.fetch_add(json.len() as u64, Ordering::Relaxed) + json.len() as u64;
+        if written > self.config.max_size / 10 {
+            self.written.store(0, Ordering::Relaxed);
+            self.prune()?;
+        }
+        Ok(())
+    }
+
+    fn entries(&self) -> io::Result<Vec<(PathBuf, u64, SystemTime)>> {
+        let read_dir = match std::fs::read_dir(&self.config.dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+        Ok(read_dir.flatten()
+            .map(|entry| entry.path())
+            .filter(|path| Self::is_entry(path))
+            .filter_map(|path| {
+                let metadata = path.metadata().ok()?;
+                Some((path, metadata.len(), metadata.modified().ok()?))
+            })
+            .collect())
+    }
+
+    fn is_entry(path: &Path) -> bool {
+        path.is_file() && path.extension().is_some_and(|extension| extension == "json")
+    }
+
+    /// Total size of the entries, in bytes.
+    pub fn size(&self) -> io::Result<u64> {
+        Ok(self.entries()?.iter().map(|(_, size, _)| size).sum())
+    }
+
+    /// Removes the least recently used entries until the cache fits in its maximum size.
+    pub fn prune(&self) -> io::Result<()> {
+        let mut entries = self.entries()?;
+        let mut size: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        entries.sort_by_key(|(_, _, modified)| *modified);
+
+        for (path, entry_size, _) in entries {
+            if size <= self.config.max_size {
+                break;
+            }
+            std::fs::remove_file(path)?;
+            size -= entry_size;
+        }
+        Ok(())
+    }
+
+    /// Removes every entry.
+    pub fn clear(&self) -> io::Result<()> {
+        for (path, _, _) in self.entries()? {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A directory of the temporary folder, removed once dropped, even when an assertion fails
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("solc-wrapper-{}-{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            TempDir(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn ast_json(path: &str) -> String {
+        format!(r#"{{"id": 1, "src": "0:0:0", "nodeType": "SourceUnit", "absolutePath": "{}", "exportedSymbols": {{}}, "nodes": []}}"#, path)
+    }
+
+    #[test]
+    fn test_ast_cache() {
+        let dir = TempDir::new("cache");
+        let cache = AstCache::new(CacheConfig { dir: dir.0.clone(), max_size: 400 });
+        let version = Version::new(0, 8, 19);
+        let key = AstCache::key(&version, &["--stop-after", "parsing"], "contract A {}");
+
+        assert_eq!(key, AstCache::key(&version, &["--stop-after", "parsing"], "contract A {}"));
+        assert_ne!(key, AstCache::key(&version, &["--stop-after", "parsing"], "contract B {}"));
+        assert_ne!(key, AstCache::key(&Version::new(0, 8, 20), &["--stop-after", "parsing"], "contract A {}"));
+        assert_ne!(key, AstCache::key(&version, &["--stop-after", "parsingcontract A {}"], ""));
+        assert!(cache.get(&key).is_none());
+
+        let warning: CompilerError = serde_json::from_value(serde_json::json!({
+            "type": "Warning",
+            "component": "general",
+            "severity": "warning",
+            "errorCode": "1878",
+            "message": "SPDX license identifier not provided in source file.",
+        })).unwrap();
+        cache.insert(&key, &ast_json("A.sol"), std::slice::from_ref(&warning)).unwrap();
+        let parsed = cache.get(&key).unwrap();
+        assert_eq!(parsed.ast.absolute_path, "A.sol");
+        assert_eq!(parsed.diagnostics, vec![warning]);
+
+        std::fs::write(cache.entry_path(&key), "{").unwrap();
+        assert!(cache.get(&key).is_none());
+        assert!(!cache.entry_path(&key).exists());
+
+        // Each entry is about 140 bytes, the oldest ones are evicted above 400
+        let keys: Vec<String> = (0..6).map(|i| AstCache::key(&version, &[], &i.to_string())).collect();
+        for (i, key) in keys.iter().enumerate() {
+            cache.insert(key, &ast_json(&format!("{}.sol", i)), &[]).unwrap();
+            std::fs::File::options().write(true).open(cache.entry_path(key)).unwrap()
+                .set_modified(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(i as u64)).unwrap();
+        }
+        cache.prune().unwrap();
+        assert!(cache.size().unwrap() <= 400);
+        assert!(cache.get(&keys[0]).is_none());
+        assert!(cache.get(&keys[5]).is_some());
+
+        cache.clear().unwrap();
+        assert_eq!(cache.size().unwrap(), 0);
+    }
+}
@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+use crate::standard_json::{Settings, StandardJsonInput};
+use crate::version::pragma::ImportResolver;
+
+/// How solc finds the files imported by the compiled sources.
+#[derive(Debug, Clone, Default)]
+pub struct CompileOptions {
+    /// Root of the source tree, imports are looked up from there. Defaults to the current directory.
+    pub base_path: Option<PathBuf>,
+    /// Additional roots for the imports, e.g. `node_modules` or `lib`.
+    pub include_paths: Vec<PathBuf>,
+    /// Import remappings, as `[context:]prefix=target`.
+    pub remappings: Vec<String>,
+    /// EVM version to compile for, the default one of the compiler otherwise.
+    pub evm_version: Option<String>,
+}
+
+impl CompileOptions {
+    fn base_path(&self) -> PathBuf {
+        self.base_path.clone().unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    pub(crate) fn args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--standard-json".to_string(),
+            "--base-path".to_string(),
+            self.base_path().to_string_lossy().to_string(),
+        ];
+        for include_path in &self.include_paths {
+            args.push("--include-path".to_string());
+            args.push(include_path.to_string_lossy().to_string());
+        }
+        args
+    }
+
+    // Where solc looks up the non-relative imports
+    pub(crate) fn roots(&self) -> Vec<PathBuf> {
+        std::iter::once(self.base_path()).chain(self.include_paths.iter().cloned()).collect()
+    }
+
+    /// Resolves the imports the same way solc does with these options.
+    pub fn import_resolver(&self) -> ImportResolver {
+        ImportResolver {
+            roots: self.roots(),
+            remappings: self.remappings.clone(),
+        }
+    }
+
+    /// Builds the standard-JSON input compiling `sources` (name and content) and only outputting their ASTs.
+    /// Requesting no bytecode makes solc stop once the sources are analysed, with typed and resolved ASTs.
+    pub fn standard_json_input(&self, sources: &[(String, String)]) -> StandardJsonInput {
+        let input = sources.iter().fold(StandardJsonInput::new(), |input, (name, content)| input.source(name, content));
+
+        input.settings(Settings {
+            remappings: self.remappings.clone(),
+            evm_version: self.evm_version.clone(),
+            output_selection: Settings::ast_output(),
+            ..Settings::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_json_input() {
+        let options = CompileOptions {
+            include_paths: vec![PathBuf::from("node_modules")],
+            remappings: vec!["@openzeppelin/=lib/openzeppelin-contracts/".to_string()],
+            ..CompileOptions::default()
+        };
+        let input = options.standard_json_input(&[("src/Token.sol".to_string(), "contract Token {}".to_string())]);
+
+        assert_eq!(input.sources["src/Token.sol"].content.as_deref(), Some("contract Token {}"));
+        assert_eq!(input.settings.remappings, vec!["@openzeppelin/=lib/openzeppelin-contracts/"]);
+        assert_eq!(input.settings.output_selection, Settings::ast_output());
+        assert_eq!(options.args(), vec!["--standard-json", "--base-path", ".", "--include-path", "node_modules"]);
+    }
+}
@@ -1,11 +1,11 @@
 use thiserror::Error;
 use crate::{solc::error::CommandError, version::error::SolcVersionError, ast::error::AstError};
-use anyhow;
 use crate::solc::parsing_error::ParsingError;
+use crate::standard_json::CompilerError;
 
 #[derive(Error, Debug)]
 pub enum SolcError {
-    #[error("SolcError: Something went wrong with sevm")]
+    #[error("SolcError: Something went wrong with sevm: {0}")]
     SevmFailed(#[from] SolcVersionError),
 
     #[error("SolcError: Error from solc")]
@@ -23,6 +23,9 @@ pub enum SolcError {
     #[error("SolcError: compiler returned an error without outputing AST")]
     ParsingFailed(#[from] ParsingError),
 
+    #[error("SolcError: compilation failed\n{}", .0.iter().map(|error| error.to_string()).collect::<Vec<String>>().join("\n"))]
+    CompilationFailed(Vec<CompilerError>),
+
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -8,12 +8,24 @@ mod version;
 
 use solc::command::SolcCommand;
 use version::version::SolcVersion;
+pub use version::version::SolcConfig;
+use version::pragma::{source_version_reqs, PragmaVersionReq};
+pub use version::pragma::ImportResolver;
 use ast::parse::parse_ast;
 
 mod error;
 pub use error::SolcError;
-use crate::solc::parsing_error::ParsingError;
-use crate::utils::{get_error_location, get_error_message};
+
+pub mod cache;
+pub use cache::{AstCache, CacheConfig};
+pub mod compile;
+pub use compile::CompileOptions;
+pub mod standard_json;
+use standard_json::{CompilerError, StandardJsonInput, StandardJsonOutput};
+pub use crate::solc::parsing_error::ParsingError;
+use crate::utils::parse_diagnostics;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 
 pub enum ExecuteResult {
@@ -21,8 +33,25 @@ pub enum ExecuteResult {
     ParsingError(ParsingError),
 }
 
+/// The AST of a parsed source, with the warnings and infos solc reported on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedSource {
+    pub ast: SourceUnit,
+    pub diagnostics: Vec<CompilerError>,
+}
+
+/// The ASTs of compiled sources, by source name, with the warnings and infos of the compilation.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Compilation {
+    pub asts: BTreeMap<String, SourceUnit>,
+    pub diagnostics: Vec<CompilerError>,
+}
+
 pub struct Solc {
-    version: SolcVersion
+    version: SolcVersion,
+    cache: Option<AstCache>,
+    // Finds the imports of the parsed files, whose pragmas the solc version must also meet
+    import_resolver: ImportResolver,
 }
 
 impl Default for Solc {
@@ -33,7 +62,27 @@ impl Default for Solc {
 
 impl Solc {
     pub fn new() -> Self {
-        Solc { version: SolcVersion::default() }
+        Solc { version: SolcVersion::default(), cache: None, import_resolver: ImportResolver::default() }
+    }
+
+    /// Uses the solc binaries of `config` instead of downloading them as needed.
+    pub fn with_config(config: SolcConfig) -> Self {
+        Solc { version: SolcVersion::with_config(config), cache: None, import_resolver: ImportResolver::default() }
+    }
+
+    /// Reuses the ASTs of the sources parsed by previous runs. Analysed sources are always compiled,
+    /// as their ASTs also depend on the files they import.
+    pub fn set_cache(&mut self, cache: Option<AstCache>) {
+        self.cache = cache;
+    }
+
+    pub fn cache(&self) -> Option<&AstCache> {
+        self.cache.as_ref()
+    }
+
+    /// Looks up the imports of the parsed files as solc does with `options`, e.g. through the remappings of the project.
+    pub fn set_import_options(&mut self, options: &CompileOptions) {
+        self.import_resolver = options.import_resolver();
     }
 
     fn skip_output_header(output: &str) -> &str {
@@ -41,75 +90,125 @@ impl Solc {
         &output[idx..]
     }
 
-    fn check_stderr(stderr: &str) -> Result<(), ParsingError> {
-        if !stderr.contains("Error") {
-            return Ok(());
+    // The diagnostics of solc, or a failure with all of them when one is an error
+    fn check_stderr(stderr: &str, content: &str) -> Result<Vec<CompilerError>, ParsingError> {
+        let errors = parse_diagnostics(stderr, content);
+
+        if errors.iter().any(|error| error.severity == standard_json::ErrorSeverity::Error) {
+            return Err(ParsingError { errors });
         }
-        let location = get_error_location(stderr);
-        let location = match location {
-            Ok(e) => e,
-            Err(_) => return Ok(())
-        };
-        let error = get_error_message(stderr);
-        let error = match error {
-            Ok(e) => e,
-            Err(_) => return Ok(())
+        Ok(errors)
+    }
+
+    // Runs solc to parse `content`, given on the standard input or as the file in `args`.
+    // Returns the AST JSON along with the warnings and infos of solc.
+    fn run_parser(solc_path: PathBuf, args: &[&str], content: &str, stdin: bool) -> Result<(String, Vec<CompilerError>), SolcError> {
+        let command = SolcCommand::new(solc_path).args(args.iter().copied());
+        let output = match stdin {
+            true => command.execute_with_input(content)?,
+            false => command.execute()?,
         };
+        let stderr = String::from_utf8(output.clone().stderr)
+            .map_err(|e| SolcError::Other(anyhow::Error::new(e)))?;
+        let diagnostics = Solc::check_stderr(stderr.as_str(), content).map_err(SolcError::ParsingFailed)?;
+        let res = String::from_utf8(output.stdout)
+            .map_err(|e| SolcError::Other(anyhow::Error::new(e)))?;
+        Ok((String::from(Self::skip_output_header(&res)), diagnostics))
+    }
 
-        Err(
-            ParsingError {
-                error,
-                location
-            }
-        )
+    fn file_parser_args(path: &str) -> [&str; 4] {
+        ["--ast-compact-json", "--stop-after", "parsing", path]
     }
 
+    const CONTENT_PARSER_ARGS: [&'static str; 4] = ["--ast-compact-json", "--stop-after", "parsing", "-"];
+
     pub fn execute_on_file(&self, path: &str) -> Result<String, SolcError> {
         let content = std::fs::read_to_string(path).map_err(|e| SolcError::Other(anyhow::Error::new(e)))?;
 
-        let version = self.version.find_matching_version( content.as_str())?;
-        let version_path = self.version.find_version_and_install(&version)?;
-
-        let output = SolcCommand::new(version_path)
-            .args(["--ast-compact-json", "--stop-after", "parsing", path])
-            .execute()?;
-        let stderr = String::from_utf8(output.clone().stderr)
-            .map_err(|e| SolcError::Other(anyhow::Error::new(e)))?;
-        let output = match Solc::check_stderr(stderr.as_str()).map_err(|e| SolcError::ParsingFailed(e)) {
-            Ok(_) => output,
-            Err(e) => return Err(e)
-        };
-        let res = String::from_utf8(output.stdout)
-            .map_err(|e| SolcError::Other(anyhow::Error::new(e)))?;
-        Ok(String::from(Self::skip_output_header(&res)))
+        let version_reqs = self.import_resolver.version_reqs(Path::new(path), &content)?;
+        let (_, version_path) = self.version.find_solc(&version_reqs)?;
+        Ok(Self::run_parser(version_path, &Self::file_parser_args(path), &content, false)?.0)
     }
 
     pub fn execute_on_content(&self, content: &str) -> Result<String, SolcError> {
-        let version = self.version.find_matching_version( content)?;
-        let version_path = self.version.find_version_and_install(&version)?;
+        let (_, version_path) = self.version.find_solc(&source_version_reqs(content)?)?;
+        Ok(Self::run_parser(version_path, &Self::CONTENT_PARSER_ARGS, content, true)?.0)
+    }
 
-        let output = SolcCommand::new(version_path)
-            .args(["--ast-compact-json", "--stop-after", "parsing", "-"])
-            .execute_with_input(content)?;
-        let stderr = String::from_utf8(output.clone().stderr)
-            .map_err(|e| SolcError::Other(anyhow::Error::new(e)))?;
-        let output = match Solc::check_stderr(stderr.as_str()).map_err(|e| SolcError::ParsingFailed(e)) {
-            Ok(_) => output,
-            Err(e) => return Err(e)
+    // Parses `content` with the version meeting `version_reqs`, unless the cache has its AST for this version and these arguments
+    fn parse_cached(&self, version_reqs: &[PragmaVersionReq], args: &[&str], content: &str, stdin: bool) -> Result<ParsedSource, SolcError> {
+        let (version, version_path) = self.version.find_solc(version_reqs)?;
+        let cached = self.cache.as_ref().map(|cache| (cache, AstCache::key(&version, args, content)));
+        if let Some(parsed) = cached.as_ref().and_then(|(cache, key)| cache.get(key)) {
+            return Ok(parsed);
+        }
+
+        let (output, diagnostics) = Self::run_parser(version_path, args, content, stdin)?;
+        let ast = parse_ast(output.as_str())?;
+        if let Some((cache, key)) = cached {
+            // The cache only saves time, failing to write it doesn't fail the parsing
+            let _ = cache.insert(&key, &output, &diagnostics);
+        }
+        Ok(ParsedSource { ast, diagnostics })
+    }
+
+    pub fn extract_ast_file(&self, filepath: String) -> Result<ParsedSource, SolcError> {
+        let content = std::fs::read_to_string(&filepath).map_err(|e| SolcError::Other(anyhow::Error::new(e)))?;
+        let version_reqs = self.import_resolver.version_reqs(Path::new(&filepath), &content)?;
+        self.parse_cached(&version_reqs, &Self::file_parser_args(&filepath), &content, false)
+    }
+
+    /// Parses `content`, the source of the file at `path` as edited, e.g. not yet saved. The path is only used to
+    /// find the files it imports, whose pragmas the compiler must also meet.
+    pub fn extract_ast_content(&self, path: &str, content: String) -> Result<ParsedSource, SolcError> {
+        let version_reqs = self.import_resolver.version_reqs(Path::new(path), &content)?;
+        self.parse_cached(&version_reqs, &Self::CONTENT_PARSER_ARGS, &content, true)
+    }
+
+    /// Runs solc on a standard-JSON input, with the version matching the pragmas of all its sources.
+    /// Only `options` paths are used, remappings and EVM version are taken from the input settings.
+    pub fn compile_standard_json(&self, input: &StandardJsonInput, options: &CompileOptions) -> Result<StandardJsonOutput, SolcError> {
+        let resolver = ImportResolver {
+            remappings: input.settings.remappings.clone(),
+            ..options.import_resolver()
         };
+        let mut version_reqs = Vec::new();
+        for (name, source) in &input.sources {
+            if let Some(content) = &source.content {
+                version_reqs.extend(resolver.version_reqs(Path::new(name), content)?);
+            }
+        }
+        let (_, version_path) = self.version.find_solc(&version_reqs)?;
+
+        let input = serde_json::to_string(input).map_err(|e| SolcError::Other(anyhow::Error::new(e)))?;
+        let output = SolcCommand::new(version_path)
+            .args(options.args())
+            .execute_with_input(&input)?;
         let res = String::from_utf8(output.stdout)
             .map_err(|e| SolcError::Other(anyhow::Error::new(e)))?;
-        Ok(String::from(Self::skip_output_header(&res)))
-    }
-
-    pub fn extract_ast_file(&self, filepath: String) -> Result<SourceUnit, SolcError> {
-        let output = self.execute_on_file(filepath.as_str())?;
-        Ok(parse_ast(output.as_str())?)
+        if res.trim().is_empty() {
+            return Err(SolcError::OutputIsEmpty);
+        }
+        Ok(serde_json::from_str(&res).map_err(ast::error::AstError::from)?)
     }
 
-    pub fn extract_ast_content(&self, content: String) -> Result<SourceUnit, SolcError> {
-        let output = self.execute_on_content(&content)?;
-        Ok(parse_ast(output.as_str())?)
+    /// Compiles `sources` (name and content) together up to the analysis, and returns the AST of every source,
+    /// imported ones included. Unlike the parse-only ASTs, they have their types and references resolved.
+    /// When the sources don't compile, fails with all the diagnostics of solc, warnings included.
+    pub fn compile_sources(&self, sources: &[(String, String)], options: &CompileOptions) -> Result<Compilation, SolcError> {
+        if sources.is_empty() {
+            return Ok(Compilation::default());
+        }
+        let output = self.compile_standard_json(&options.standard_json_input(sources), options)?;
+        if output.has_errors() {
+            return Err(SolcError::CompilationFailed(output.errors));
+        }
+        Ok(Compilation {
+            asts: output.sources.into_iter()
+                .filter_map(|(name, source)| Some((name, source.ast?)))
+                .collect(),
+            diagnostics: output.errors,
+        })
     }
 
 }
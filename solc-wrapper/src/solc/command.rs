@@ -54,6 +54,7 @@ impl SolcCommand {
             .args(&self.args)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
             .spawn()
             .map_err(|e| CommandError { error: e.to_string(), command_type: CommandType::ParseStdin })?;
 
@@ -1,29 +1,15 @@
 use thiserror::Error;
+use crate::standard_json::CompilerError;
 
-
-
-#[derive(Error, Debug)]
-pub struct ErrorLocation {
-    pub file: String,
-    pub line: usize,
-    pub column: usize,
-    pub length: usize
-}
-
+/// The diagnostics of solc when it failed to parse a source, at least one of them is an error.
 #[derive(Error, Debug)]
 pub struct ParsingError {
-    pub error: String,
-    pub location: ErrorLocation,
-}
-
-impl std::fmt::Display for ErrorLocation {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "ErrorLocation in {} at line {}, column {}", self.file, self.line, self.column)
-    }
+    pub errors: Vec<CompilerError>,
 }
 
 impl std::fmt::Display for ParsingError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "ParsingError: {}", self.error)
+        let errors: Vec<String> = self.errors.iter().map(|error| error.to_string()).collect();
+        write!(f, "ParsingError: {}", errors.join("\n"))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,203 @@
+//! Types of the solc standard-JSON interface, see https://docs.soliditylang.org/en/latest/using-the-compiler.html#compiler-input-and-output-json-description
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::ast::ast::SourceUnit;
+
+/// For each source name (`*` for all), the outputs requested for each contract (`*` for all, `""` for the source itself).
+pub type OutputSelection = BTreeMap<String, BTreeMap<String, Vec<String>>>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandardJsonInput {
+    pub language: String,
+    pub sources: BTreeMap<String, InputSource>,
+    #[serde(default)]
+    pub settings: Settings,
+}
+
+impl Default for StandardJsonInput {
+    fn default() -> Self {
+        StandardJsonInput {
+            language: "Solidity".to_string(),
+            sources: BTreeMap::new(),
+            settings: Settings::default(),
+        }
+    }
+}
+
+impl StandardJsonInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn source(mut self, name: impl Into<String>, content: impl Into<String>) -> Self {
+        self.sources.insert(name.into(), InputSource { content: Some(content.into()), urls: vec![] });
+        self
+    }
+
+    pub fn settings(mut self, settings: Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+}
+
+/// A source given by its content, or by URLs solc loads it from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputSource {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub urls: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    /// Only `parsing` is supported by solc.
+    #[serde(rename = "stopAfter", skip_serializing_if = "Option::is_none")]
+    pub stop_after: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remappings: Vec<String>,
+    #[serde(rename = "evmVersion", skip_serializing_if = "Option::is_none")]
+    pub evm_version: Option<String>,
+    #[serde(rename = "outputSelection", default)]
+    pub output_selection: OutputSelection,
+}
+
+impl Settings {
+    /// Selects the AST of every source, and nothing else.
+    pub fn ast_output() -> OutputSelection {
+        BTreeMap::from([("*".to_string(), BTreeMap::from([(String::new(), vec!["ast".to_string()])]))])
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandardJsonOutput {
+    #[serde(default)]
+    pub errors: Vec<CompilerError>,
+    #[serde(default)]
+    pub sources: BTreeMap<String, OutputSource>,
+}
+
+impl StandardJsonOutput {
+    pub fn has_errors(&self) -> bool {
+        self.errors.iter().any(|error| error.severity == ErrorSeverity::Error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputSource {
+    pub id: usize,
+    pub ast: Option<SourceUnit>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// A byte range of a source, `start` and `end` are -1 when unknown.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorSourceLocation {
+    pub file: String,
+    pub start: i64,
+    pub end: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// An error, warning or info reported by solc.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompilerError {
+    #[serde(rename = "sourceLocation", skip_serializing_if = "Option::is_none")]
+    pub source_location: Option<ErrorSourceLocation>,
+    #[serde(rename = "secondarySourceLocations", default, skip_serializing_if = "Vec::is_empty")]
+    pub secondary_source_locations: Vec<ErrorSourceLocation>,
+    /// e.g. `TypeError`, `ParserError` or `Warning`.
+    #[serde(rename = "type")]
+    pub error_type: String,
+    pub component: String,
+    pub severity: ErrorSeverity,
+    #[serde(rename = "errorCode", skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    pub message: String,
+    #[serde(rename = "formattedMessage", skip_serializing_if = "Option::is_none")]
+    pub formatted_message: Option<String>,
+}
+
+impl std::fmt::Display for CompilerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.formatted_message {
+            Some(message) => write!(f, "{}", message.trim_end()),
+            None => write!(f, "{}: {}", self.error_type, self.message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+
+    #[test]
+    fn test_serialize_input() {
+        let input = StandardJsonInput::new()
+            .source("src/Token.sol", "contract Token {}")
+            .settings(Settings {
+                remappings: vec!["@openzeppelin/=lib/openzeppelin-contracts/".to_string()],
+                evm_version: Some("paris".to_string()),
+                output_selection: Settings::ast_output(),
+                ..Settings::default()
+            });
+
+        assert_eq!(serde_json::to_value(&input).unwrap(), json!({
+            "language": "Solidity",
+            "sources": { "src/Token.sol": { "content": "contract Token {}" } },
+            "settings": {
+                "remappings": ["@openzeppelin/=lib/openzeppelin-contracts/"],
+                "evmVersion": "paris",
+                "outputSelection": { "*": { "": ["ast"] } },
+            },
+        }));
+    }
+
+    #[test]
+    fn test_deserialize_output() {
+        let output: StandardJsonOutput = serde_json::from_value(json!({
+            "errors": [
+                {
+                    "component": "general",
+                    "errorCode": "2072",
+                    "formattedMessage": "Warning: Unused local variable.\n",
+                    "message": "Unused local variable.",
+                    "severity": "warning",
+                    "sourceLocation": { "end": 94, "file": "Token.sol", "start": 88 },
+                    "type": "Warning",
+                },
+                {
+                    "component": "general",
+                    "errorCode": "7576",
+                    "message": "Undeclared identifier.",
+                    "severity": "error",
+                    "type": "DeclarationError",
+                },
+            ],
+            "sources": {
+                "Token.sol": {
+                    "id": 0,
+                    "ast": { "id": 1, "src": "0:17:0", "nodeType": "SourceUnit", "absolutePath": "Token.sol", "exportedSymbols": {}, "nodes": [] },
+                },
+            },
+        })).unwrap();
+
+        assert!(output.has_errors());
+        assert_eq!(output.errors[0].severity, ErrorSeverity::Warning);
+        assert_eq!(output.errors[0].error_code.as_deref(), Some("2072"));
+        assert_eq!(output.errors[0].source_location.as_ref().map(|location| (location.start, location.end)), Some((88, 94)));
+        assert_eq!(output.errors[0].to_string(), "Warning: Unused local variable.");
+        assert_eq!(output.errors[1].to_string(), "DeclarationError: Undeclared identifier.");
+        assert_eq!(output.sources["Token.sol"].ast.as_ref().map(|ast| ast.absolute_path.as_str()), Some("Token.sol"));
+    }
+}
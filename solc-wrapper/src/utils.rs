@@ -1,71 +1,102 @@
-use regex::{Match, Regex};
+use regex::Regex;
 use once_cell::sync::Lazy;
-use crate::solc::parsing_error::{ErrorLocation};
+use crate::ast::ast::{offset_from_location, CodeLocation};
+use crate::standard_json::{CompilerError, ErrorSeverity, ErrorSourceLocation};
 
-pub static RE_SOL_ERROR_PRINT: Lazy<Regex> = Lazy::new(|| Regex::new(r"Error: (.*)\n").unwrap());
-pub static RE_SOL_ERROR_FILE: Lazy<Regex> = Lazy::new(|| Regex::new(r"--> (?P<file>.*):(?P<line>\d+):(?P<column>\d+):").unwrap());
-pub static RE_SOL_PRAGMA_VERSION: Lazy<Regex> = Lazy::new(|| Regex::new(r"pragma\s+solidity\s+(?P<version>\^?\d+\.\d+(?:\.\d+)?);").unwrap());
+// The header of a diagnostic, e.g. `ParserError: Expected ';' but got '}'` or `Warning: This declaration shadows...`
+pub static RE_SOL_DIAGNOSTIC: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?P<type>\w*(?:Error|Warning|Info))(?: \((?P<code>\d+)\))?: (?P<message>.*)$").unwrap());
+pub static RE_SOL_DIAGNOSTIC_FILE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*--> (?P<file>.*):(?P<line>\d+):(?P<column>\d+):$").unwrap());
 
-pub fn find_version_pragma(contract: &str) -> Option<Match> {
-    RE_SOL_PRAGMA_VERSION.captures(contract)?.name("version")
+fn severity(error_type: &str) -> ErrorSeverity {
+    match error_type {
+        "Warning" => ErrorSeverity::Warning,
+        "Info" => ErrorSeverity::Info,
+        _ => ErrorSeverity::Error,
+    }
+}
+
+// Byte offset in `content` of a 1-based line and column
+fn offset(content: &str, line: usize, column: usize) -> usize {
+    let offset = offset_from_location(content, &CodeLocation { line, column: column.saturating_sub(1), length: 0 });
+    offset.min(content.len())
 }
 
-fn count_length(error: String) -> usize {
-    let mut count = 0;
-    let mut n = error.len() - 3;
+/// Parses every diagnostic printed by solc on stderr, locating them in `content`, the source that was compiled.
+/// Each diagnostic is a header, followed by its location and the highlighted code:
+/// ```text
+/// ParserError: Expected ';' but got '}'
+///  --> contracts/Token.sol:5:1:
+///   |
+/// 5 | }
+///   | ^
+/// ```
+pub fn parse_diagnostics(stderr: &str, content: &str) -> Vec<CompilerError> {
+    let mut diagnostics: Vec<CompilerError> = Vec::new();
+    // Offset of the location of the last diagnostic, its length is given by the carets
+    let mut start: Option<usize> = None;
 
-    while n > 0 {
-        if error.chars().nth(n).unwrap() == '\n' {
-            return count;
-        }
-        if error.chars().nth(n).unwrap() == '^' {
-            count += 1;
+    for line in stderr.lines() {
+        if let Some(header) = RE_SOL_DIAGNOSTIC.captures(line) {
+            start = None;
+            diagnostics.push(CompilerError {
+                source_location: None,
+                secondary_source_locations: vec![],
+                error_type: header["type"].to_string(),
+                component: "general".to_string(),
+                severity: severity(&header["type"]),
+                error_code: header.name("code").map(|code| code.as_str().to_string()),
+                message: header["message"].to_string(),
+                formatted_message: None,
+            });
+        } else if let Some(location) = RE_SOL_DIAGNOSTIC_FILE.captures(line) {
+            let diagnostic = match diagnostics.last_mut() {
+                Some(diagnostic) if diagnostic.source_location.is_none() => diagnostic,
+                _ => continue,
+            };
+            // The regex only matches numbers
+            let offset = offset(content, location["line"].parse().unwrap(), location["column"].parse().unwrap());
+            start = Some(offset);
+            diagnostic.source_location = Some(ErrorSourceLocation {
+                file: location["file"].to_string(),
+                start: offset as i64,
+                end: offset as i64,
+                message: None,
+            });
+        } else if let (Some(offset), Some((_, code))) = (start, line.split_once('|')) {
+            if !code.contains('^') {
+                continue;
+            }
+            let length = code.chars().filter(|c| *c == '^').count();
+            if let Some(location) = diagnostics.last_mut().and_then(|diagnostic| diagnostic.source_location.as_mut()) {
+                location.end = (offset + length).min(content.len()) as i64;
+            }
+            start = None;
         }
-        n-=1;
     }
-    return count;
+    diagnostics
 }
 
-pub fn get_error_message(stderr: &str) -> Result<String, ()> {
-    let error = RE_SOL_ERROR_PRINT.captures(stderr);
-    let error = match error {
-        Some(error) => error.get(0),
-        None => return Err(())
-    };
-    let error = match error {
-        Some(error) => error.as_str(),
-        None => return Err(())
-    };
-    let error = error.to_string();
-    Ok(error)
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-pub fn get_error_location(stderr: &str) -> Result<ErrorLocation, ()> {
-    let error = RE_SOL_ERROR_FILE.captures(stderr);
-    let error = match error {
-        Some(error) => error,
-        None => return Err(())
-    };
-    let file = match error.name("file") {
-        Some(file) => file.as_str().to_string(),
-        None => return Err(())
-    };
-    let line = match error.name("line") {
-        Some(line) => line.as_str().to_string(),
-        None => return Err(())
-    };
-    let column = match error.name("column") {
-        Some(line) => line.as_str().to_string(),
-        None => return Err(())
-    };
-    let length = count_length(stderr.to_string());
+    #[test]
+    fn test_parse_diagnostics() {
+        let content = "pragma solidity ^0.8.0;\n\ncontract A {\n    uint a = 1\n}\n\ncontract B {\n    function f() {}\n}\n";
+        let stderr = "ParserError: Expected ';' but got '}'\n --> A.sol:5:1:\n  |\n5 | }\n  | ^\n\n\
+            Warning: Unreachable code.\n --> A.sol:8:5:\n  |\n8 |     function f() {}\n  |     ^^^^^^^^\n\n\
+            Error: No input files given.\n";
+        let diagnostics = parse_diagnostics(stderr, content);
+        let range = |diagnostic: &CompilerError| diagnostic.source_location.as_ref().map(|location| (location.start, location.end));
 
-    Ok(
-        ErrorLocation {
-            file,
-            line: line.parse().unwrap(), //unwrap is safe due to the regex that matches only number
-            column: column.parse().unwrap(), //unwrap is safe due to the regex that matches only number
-            length: length
-        }
-    )
-}
\ No newline at end of file
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics[0].error_type, "ParserError");
+        assert_eq!(diagnostics[0].severity, ErrorSeverity::Error);
+        assert_eq!(diagnostics[0].message, "Expected ';' but got '}'");
+        assert_eq!(range(&diagnostics[0]), Some((53, 54)));
+        assert_eq!(diagnostics[1].severity, ErrorSeverity::Warning);
+        assert_eq!(range(&diagnostics[1]), Some((73, 81)));
+        assert_eq!(&content[73..81], "function");
+        assert_eq!(range(&diagnostics[2]), None);
+    }
+}
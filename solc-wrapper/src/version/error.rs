@@ -1,7 +1,8 @@
 use svm_lib::SolcVmError;
 use thiserror::Error;
 
-use semver::Error;
+use semver::{Error, Version};
+use std::path::PathBuf;
 
 #[derive(Error, Debug)]
 pub enum SolcVersionError {
@@ -16,4 +17,14 @@ pub enum SolcVersionError {
 
     #[error("SolcVersionError: Wrong version of solidity")]
     WrongVersion,
+
+    #[error("SolcVersionError: Invalid solidity version requirement '{0}'")]
+    InvalidPragma(String),
+
+    #[error("SolcVersionError: No solc version matching {requirement} is installed (installed: {})",
+        if installed.is_empty() { "none".to_string() } else { installed.iter().map(|version| version.to_string()).collect::<Vec<String>>().join(", ") })]
+    NoMatchingVersion { requirement: String, installed: Vec<Version> },
+
+    #[error("SolcVersionError: No solc binary found at {}", .0.display())]
+    SolcNotFound(PathBuf),
 }
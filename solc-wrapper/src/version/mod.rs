@@ -1,2 +1,4 @@
 pub mod error;
-pub mod version;
\ No newline at end of file
+#[allow(clippy::module_inception)]
+pub mod version;
+pub mod pragma;
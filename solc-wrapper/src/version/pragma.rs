@@ -0,0 +1,190 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use semver::{Version, VersionReq};
+
+use super::error::SolcVersionError;
+
+static RE_SOL_PRAGMA_VERSION: Lazy<Regex> = Lazy::new(|| Regex::new(r"pragma\s+solidity\s+(?P<version>[^;]+);").unwrap());
+static RE_SOL_IMPORT: Lazy<Regex> = Lazy::new(|| Regex::new(r#"import\s+(?:[^;'"]*\s+from\s+)?["'](?P<path>[^"']+)["']"#).unwrap());
+static RE_SOL_COMMENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)//[^\n]*|/\*.*?\*/").unwrap());
+// An operator followed by spaces, which Solidity allows but semver doesn't
+static RE_OPERATOR_SPACES: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\^|~|>=|<=|>|<|=)\s+").unwrap());
+
+/// The version requirement of a `pragma solidity`, e.g. `^0.8.0`, `>=0.7.0 <0.9.0` or `0.6.12 || ^0.8.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PragmaVersionReq {
+    // The requirement is met when one of them is
+    alternatives: Vec<VersionReq>,
+}
+
+impl PragmaVersionReq {
+    pub fn parse(pragma: &str) -> Result<Self, SolcVersionError> {
+        let invalid = || SolcVersionError::InvalidPragma(pragma.trim().to_string());
+        let pragma = RE_OPERATOR_SPACES.replace_all(pragma.trim(), "$1");
+
+        let alternatives = pragma.split("||").map(|range| {
+            let comparators: Vec<String> = match range.split_once(" - ") {
+                Some((lower, upper)) => vec![format!(">={}", lower.trim()), format!("<={}", upper.trim())],
+                None => range.split_whitespace().map(Self::comparator).collect(),
+            };
+            if comparators.is_empty() {
+                return Err(invalid());
+            }
+            VersionReq::parse(&comparators.join(",")).map_err(|_| invalid())
+        }).collect::<Result<Vec<VersionReq>, SolcVersionError>>()?;
+
+        Ok(PragmaVersionReq { alternatives })
+    }
+
+    // Solidity versions without an operator are exact, while semver treats them as caret requirements
+    fn comparator(version: &str) -> String {
+        let has_operator = version.starts_with(['^', '~', '>', '<', '=']);
+        let is_wildcard = version.contains(['*', 'x', 'X']);
+        match has_operator || is_wildcard {
+            true => version.to_string(),
+            false => format!("={}", version),
+        }
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.alternatives.iter().any(|req| req.matches(version))
+    }
+}
+
+impl std::fmt::Display for PragmaVersionReq {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let alternatives: Vec<String> = self.alternatives.iter().map(|req| req.to_string()).collect();
+        write!(f, "{}", alternatives.join(" || "))
+    }
+}
+
+fn strip_comments(source: &str) -> std::borrow::Cow<'_, str> {
+    RE_SOL_COMMENT.replace_all(source, "")
+}
+
+/// The requirements of every `pragma solidity` of a source, a version must meet all of them.
+pub fn source_version_reqs(source: &str) -> Result<Vec<PragmaVersionReq>, SolcVersionError> {
+    RE_SOL_PRAGMA_VERSION.captures_iter(&strip_comments(source))
+        .map(|pragma| PragmaVersionReq::parse(&pragma["version"]))
+        .collect()
+}
+
+/// Finds the files imported by a source, the same way solc does.
+#[derive(Debug, Clone, Default)]
+pub struct ImportResolver {
+    /// The base path and the include paths, where non-relative imports are looked up.
+    pub roots: Vec<PathBuf>,
+    /// Import remappings, as `[context:]prefix=target`.
+    pub remappings: Vec<String>,
+}
+
+impl ImportResolver {
+    // The paths `import` may refer to, in the order solc looks them up
+    fn candidates(&self, importer: &Path, import: &str) -> Vec<PathBuf> {
+        if import.starts_with("./") || import.starts_with("../") {
+            return vec![importer.parent().unwrap_or(Path::new("")).join(import)];
+        }
+        // The longest matching prefix wins
+        let remapping = self.remappings.iter()
+            .filter_map(|remapping| {
+                let remapping = remapping.split_once(':').map_or(remapping.as_str(), |(_, remapping)| remapping);
+                remapping.split_once('=')
+            })
+            .filter(|(prefix, _)| import.starts_with(prefix))
+            .max_by_key(|(prefix, _)| prefix.len());
+        let import = match remapping {
+            Some((prefix, target)) => format!("{}{}", target, &import[prefix.len()..]),
+            None => import.to_string(),
+        };
+        let mut candidates = vec![PathBuf::from(&import)];
+        candidates.extend(self.roots.iter().map(|root| root.join(&import)));
+        candidates
+    }
+
+    /// The file `import`, as written in the source at `importer`, refers to, if it exists.
+    /// Relative imports are looked up next to the importer, the other ones are remapped then looked up in the roots.
+    pub fn resolve(&self, importer: &Path, import: &str) -> Option<PathBuf> {
+        self.candidates(importer, import).into_iter().find(|candidate| candidate.is_file())
+    }
+
+    /// The requirements of the pragmas of a source and of the files it imports, recursively.
+    /// Imports that can't be read are skipped, solc reports them.
+    pub fn version_reqs(&self, path: &Path, source: &str) -> Result<Vec<PragmaVersionReq>, SolcVersionError> {
+        let mut reqs = Vec::new();
+        let mut visited: HashSet<PathBuf> = path.canonicalize().into_iter().collect();
+        let mut pending = vec![(path.to_path_buf(), source.to_string())];
+
+        while let Some((path, source)) = pending.pop() {
+            for req in source_version_reqs(&source)? {
+                if !reqs.contains(&req) {
+                    reqs.push(req);
+                }
+            }
+            for import in RE_SOL_IMPORT.captures_iter(&strip_comments(&source)) {
+                let imported = self.candidates(&path, &import["path"]).into_iter()
+                    .find_map(|candidate| Some((candidate.canonicalize().ok()?, std::fs::read_to_string(&candidate).ok()?)));
+                if let Some((imported, content)) = imported {
+                    if visited.insert(imported.clone()) {
+                        pending.push((imported, content));
+                    }
+                }
+            }
+        }
+        Ok(reqs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pragma: &str, version: &str) -> bool {
+        PragmaVersionReq::parse(pragma).unwrap().matches(&Version::parse(version).unwrap())
+    }
+
+    #[test]
+    fn test_parse_pragma() {
+        assert!(matches("^0.8.0", "0.8.19"));
+        assert!(!matches("^0.8.0", "0.9.0"));
+        assert!(matches("0.8.19", "0.8.19"));
+        assert!(!matches("0.8.19", "0.8.20"));
+        assert!(matches("0.8", "0.8.20"));
+        assert!(matches(">=0.8.0 <0.9.0", "0.8.4"));
+        assert!(!matches(">=0.8.0 <0.9.0", "0.9.0"));
+        assert!(matches(">= 0.6.0 < 0.8.0", "0.7.6"));
+        assert!(matches("0.6.12 || ^0.8.0", "0.6.12"));
+        assert!(matches("0.6.12 || ^0.8.0", "0.8.1"));
+        assert!(!matches("0.6.12 || ^0.8.0", "0.7.0"));
+        assert!(matches("0.8.0 - 0.8.10", "0.8.10"));
+        assert!(!matches("0.8.0 - 0.8.10", "0.8.11"));
+        assert!(matches("~0.7.1", "0.7.6"));
+        assert!(matches("0.8.x", "0.8.3"));
+        assert!(matches("*", "0.4.26"));
+        assert!(PragmaVersionReq::parse(">=0.8.0 ||").is_err());
+        assert!(PragmaVersionReq::parse("latest").is_err());
+    }
+
+    #[test]
+    fn test_source_version_reqs() {
+        let source = "// pragma solidity ^0.4.0;\npragma solidity >=0.8.0;\n/* pragma solidity 0.5.0; */\npragma solidity <0.8.20;\ncontract A {}";
+        let reqs = source_version_reqs(source).unwrap();
+
+        assert_eq!(reqs.iter().map(|req| req.to_string()).collect::<Vec<String>>(), vec![">=0.8.0", "<0.8.20"]);
+        assert!(source_version_reqs("contract A {}").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_version_reqs_with_imports() {
+        let root = Path::new("tests/files/imports");
+        let source = "pragma solidity <0.9.0;\nimport {Base} from \"./Base.sol\";\nimport \"@oz/ERC20.sol\";\nimport \"missing.sol\";";
+        let resolver = ImportResolver { roots: vec![root.to_path_buf()], remappings: vec!["@oz/=lib/oz/".to_string()] };
+
+        let reqs = resolver.version_reqs(&root.join("src/Token.sol"), source).unwrap();
+
+        assert_eq!(reqs.iter().map(|req| req.to_string()).collect::<Vec<String>>(), vec!["<0.9.0", "^0.8.0", ">=0.8.4"]);
+        assert_eq!(resolver.resolve(&root.join("src/Token.sol"), "@oz/ERC20.sol"), Some(root.join("lib/oz/ERC20.sol")));
+        assert_eq!(resolver.resolve(&root.join("src/Token.sol"), "missing.sol"), None);
+    }
+}
@@ -1,12 +1,34 @@
-use crate::utils;
 
-use semver::{Version, VersionReq};
-use std::{path::PathBuf};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use semver::Version;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
 
 use super::error::SolcVersionError;
+use super::pragma::PragmaVersionReq;
+
+// Files may be parsed concurrently, only one of them should install a given solc version
+static INSTALL_LOCK: Mutex<()> = Mutex::new(());
+
+// The version in the name of a binary, or in the output of `solc --version`
+static RE_SOLC_VERSION: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:solc-v?|Version: )(?P<version>\d+\.\d+\.\d+)").unwrap());
+
+/// Where the solc binaries come from.
+#[derive(Debug, Clone, Default)]
+pub struct SolcConfig {
+    /// Only the installed compilers are used, nothing is downloaded.
+    pub offline: bool,
+    /// A solc binary, or a directory of binaries, used instead of the ones managed by svm.
+    pub solc_path: Option<PathBuf>,
+    /// Version requirement, as in a `pragma solidity`, for the sources without pragma.
+    pub default_version: Option<String>,
+}
 
 pub struct SolcVersion {
-    global_version_path: PathBuf
+    global_version_path: PathBuf,
+    config: SolcConfig,
 }
 
 impl Default for SolcVersion {
@@ -17,22 +39,57 @@ impl Default for SolcVersion {
 
 impl SolcVersion {
     pub fn new(path: impl Into<PathBuf>) -> Self {
-        SolcVersion { global_version_path: path.into() }
+        SolcVersion { global_version_path: path.into(), config: SolcConfig::default() }
     }
 
-    pub fn find_matching_version(&self, source: &str) -> Result<Version, SolcVersionError> {
-        let version_req = Self::source_version_req(source)?;
+    pub fn with_config(config: SolcConfig) -> Self {
+        SolcVersion { config, ..Self::default() }
+    }
 
-        if self.global_version_path.is_file() {
-            let installed_versions = Self::list_installed_versions()?;
-            let version = installed_versions.iter().find(|v| version_req.matches(v));
-            if !version.is_none() {
-                return version.cloned().ok_or(SolcVersionError::ComputationFailed);
+    /// Finds the binary of the newest version meeting all the requirements, the ones of the pragmas of the sources to compile
+    /// together and of their imports. The default version is used when there are none.
+    /// Unless offline, a matching version is installed when none is.
+    pub fn find_solc(&self, version_reqs: &[PragmaVersionReq]) -> Result<(Version, PathBuf), SolcVersionError> {
+        let default_version;
+        let version_reqs = match (version_reqs.is_empty(), &self.config.default_version) {
+            (false, _) => version_reqs,
+            (true, Some(version)) => {
+                default_version = [PragmaVersionReq::parse(version)?];
+                &default_version[..]
             }
+            (true, None) => return Err(SolcVersionError::WrongVersion),
+        };
+        let matches = |version: &Version| version_reqs.iter().all(|req| req.matches(version));
+        let no_matching_version = |installed: Vec<Version>| SolcVersionError::NoMatchingVersion {
+            requirement: version_reqs.iter().map(|req| req.to_string()).collect::<Vec<String>>().join(" and "),
+            installed,
+        };
+
+        if let Some(solc_path) = &self.config.solc_path {
+            let binaries = Self::list_local_binaries(solc_path)?;
+            return match binaries.iter().find(|(version, _)| matches(version)) {
+                Some(binary) => Ok(binary.clone()),
+                None => Err(no_matching_version(binaries.into_iter().map(|(version, _)| version).collect())),
+            };
+        }
+
+        // TODO optimize the code to only have to run it once and outside this function possibly
+        let _guard = INSTALL_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut installed_versions = match self.global_version_path.is_file() || self.config.offline {
+            true => Self::list_installed_versions().unwrap_or_default(),
+            false => Vec::new(),
+        };
+        installed_versions.sort_by(|a, b| b.cmp(a));
+        if let Some(version) = installed_versions.iter().find(|version| matches(version)) {
+            let path = svm_lib::version_path(&version.to_string()).join("solc-".to_owned() + version.to_string().as_str());
+            return Ok((version.clone(), path));
+        }
+        if self.config.offline {
+            return Err(no_matching_version(installed_versions));
         }
         let remote_versions = Self::list_remote_versions()?;
-        let version = remote_versions.iter().find(|v| version_req.matches(v));
-        version.cloned().ok_or(SolcVersionError::ComputationFailed)
+        let version = remote_versions.iter().filter(|version| matches(version)).max().ok_or(SolcVersionError::ComputationFailed)?;
+        Ok((version.clone(), Self::install_version(version)?))
     }
 
     pub fn list_installed_versions() -> Result<Vec<Version>, SolcVersionError> {
@@ -51,37 +108,71 @@ impl SolcVersion {
         Ok(svm_lib::blocking_install(version)?)
     }
 
-    pub fn find_version_and_install(&self, version: &Version) -> Result<PathBuf, SolcVersionError> {
-        // TODO optimize the code to only have to run it once and outside this function possibly
-        if self.global_version_path.is_file() {
-            let versions = svm_lib::installed_versions()?;
-            if !versions.is_empty() && versions.contains(&version) {
-                return Ok(svm_lib::version_path(&version.to_string()).join("solc-".to_owned() + version.to_string().as_str()));
+    /// Lists the solc binaries at `path` with their version, the newest first.
+    /// `path` is either a binary or a directory of binaries named `solc*`, possibly in sub-directories as installed by svm.
+    pub fn list_local_binaries(path: &Path) -> Result<Vec<(Version, PathBuf)>, SolcVersionError> {
+        if path.is_file() {
+            return Ok(vec![(Self::binary_version(path)?, path.to_path_buf())]);
+        }
+        let read_dir = |dir: &Path| std::fs::read_dir(dir)
+            .map_err(|_| SolcVersionError::SolcNotFound(dir.to_path_buf()));
+
+        let mut binaries = Vec::new();
+        for entry in read_dir(path)?.flatten() {
+            let entry_path = entry.path();
+            let files = match entry_path.is_dir() {
+                true => read_dir(&entry_path)?.flatten().map(|entry| entry.path()).collect(),
+                false => vec![entry_path],
+            };
+            for file in files {
+                let is_solc = file.file_name().is_some_and(|name| name.to_string_lossy().starts_with("solc"));
+                if file.is_file() && is_solc {
+                    if let Ok(version) = Self::binary_version(&file) {
+                        binaries.push((version, file));
+                    }
+                }
             }
         }
-        Self::install_version(version)
+        binaries.sort_by(|a, b| b.0.cmp(&a.0));
+        Ok(binaries)
     }
 
-    pub fn source_version_req(source: &str) -> Result<VersionReq, SolcVersionError> {
-        let version =
-            utils::find_version_pragma(source).ok_or(SolcVersionError::WrongVersion)?;
-        Self::version_req(version.as_str())
+    // The version of a binary, from its name (e.g. `solc-0.8.19` or `solc-v0.8.19`) or by running it
+    fn binary_version(path: &Path) -> Result<Version, SolcVersionError> {
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        if let Some(version) = RE_SOLC_VERSION.captures(&name) {
+            return Ok(Version::parse(&version["version"])?);
+        }
+        let output = Command::new(path).arg("--version").output()
+            .map_err(|_| SolcVersionError::SolcNotFound(path.to_path_buf()))?;
+        let output = String::from_utf8_lossy(&output.stdout);
+        let version = RE_SOLC_VERSION.captures(&output).ok_or(SolcVersionError::SolcNotFound(path.to_path_buf()))?;
+        Ok(Version::parse(&version["version"])?)
     }
+}
 
-    /// Returns the corresponding SemVer version requirement for the solidity version
-    pub fn version_req(version: &str) -> Result<VersionReq, SolcVersionError> {
-        let version = version.replace(' ', ",");
-
-        // Somehow, Solidity semver without an operator is considered to be "exact",
-        // but lack of operator automatically marks the operator as Caret, so we need
-        // to manually patch it? :shrug:
-        let exact = !matches!(&version[0..1], "*" | "^" | "=" | ">" | "<" | "~");
-        let mut version = VersionReq::parse(&version)?;
-        if exact {
-            version.comparators[0].op = semver::Op::Exact;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_solc_in_directory() {
+        let root = PathBuf::from("tests/files/solc");
+        let version = SolcVersion::with_config(SolcConfig { offline: true, solc_path: Some(root.clone()), default_version: None });
+
+        let reqs = |pragmas: &[&str]| pragmas.iter().map(|pragma| PragmaVersionReq::parse(pragma).unwrap()).collect::<Vec<_>>();
+
+        assert_eq!(version.find_solc(&reqs(&["^0.8.0"])).unwrap(), (Version::new(0, 8, 20), root.join("0.8.20/solc-0.8.20")));
+        assert_eq!(version.find_solc(&reqs(&["^0.8.0", ">=0.7.0 <=0.8.19"])).unwrap(), (Version::new(0, 8, 19), root.join("solc-0.8.19")));
+        assert!(matches!(version.find_solc(&[]), Err(SolcVersionError::WrongVersion)));
+        match version.find_solc(&reqs(&["^0.6.0 || 0.5.0"])) {
+            Err(e @ SolcVersionError::NoMatchingVersion { .. }) => assert_eq!(
+                e.to_string(),
+                "SolcVersionError: No solc version matching ^0.6.0 || =0.5.0 is installed (installed: 0.8.20, 0.8.19, 0.7.6)"
+            ),
+            res => panic!("unexpected result: {:?}", res),
         }
-
-        Ok(version)
+        let version = SolcVersion::with_config(SolcConfig { default_version: Some("0.7.6".to_string()), ..version.config });
+        assert_eq!(version.find_solc(&[]).unwrap(), (Version::new(0, 7, 6), root.join("solc-v0.7.6")));
     }
-
-}
\ No newline at end of file
+}
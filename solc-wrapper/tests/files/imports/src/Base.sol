@@ -0,0 +1,2 @@
+pragma solidity >=0.8.4;
+import "./Token.sol";
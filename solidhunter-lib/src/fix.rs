@@ -0,0 +1,164 @@
+use crate::offset_from_range;
+use crate::range_from_offset;
+use crate::types::{LintDiag, TextEdit};
+
+pub struct FixResult {
+    pub content: String,
+    pub applied: usize,
+}
+
+pub struct FixReport {
+    /// The content once every applicable fix has been applied.
+    pub content: String,
+    /// The number of fixes applied over all the passes.
+    pub applied: usize,
+    /// The diagnostics remaining on the fixed content.
+    pub diags: Vec<LintDiag>,
+}
+
+// An edit resolved to byte offsets in the content it applies to
+struct OffsetEdit<'a> {
+    start: usize,
+    end: usize,
+    new_text: &'a str,
+}
+
+fn resolve_edit<'a>(content: &str, edit: &'a TextEdit) -> Option<OffsetEdit<'a>> {
+    let start = offset_from_range(content, &edit.range);
+    let end = start + edit.range.length as usize;
+
+    if end > content.len() || !content.is_char_boundary(start) || !content.is_char_boundary(end) {
+        return None;
+    }
+    Some(OffsetEdit { start, end, new_text: edit.new_text.as_str() })
+}
+
+fn overlaps(a: &OffsetEdit, b: &OffsetEdit) -> bool {
+    // Two insertions at the same place would have an unspecified order
+    a.start == b.start || (a.start < b.end && b.start < a.end)
+}
+
+/// Decodes the `src` location of an AST node (`start:length:file`) to its byte offset and length.
+pub fn decode_src(src: &str) -> Option<(usize, usize)> {
+    let mut parts = src.split(':');
+    let start = parts.next()?.parse::<usize>().ok()?;
+    let length = parts.next()?.parse::<usize>().ok()?;
+
+    Some((start, length))
+}
+
+/// Builds an edit replacing the `src` location of an AST node by `new_text`,
+/// only if the replaced text is `expected`.
+pub fn replace_src(content: &str, src: &str, expected: &str, new_text: &str) -> Option<TextEdit> {
+    let (start, length) = decode_src(src)?;
+
+    if content.get(start..start + length)? != expected {
+        return None;
+    }
+    Some(TextEdit {
+        range: range_from_offset(content, start, length),
+        new_text: new_text.to_string(),
+    })
+}
+
+/// Applies the fixes attached to `diags` on `content`.
+/// The edits of a diagnostic are applied together, and a fix is skipped when one of
+/// its edits overlaps an edit already accepted; it will be proposed again on the next lint pass.
+pub fn apply_fixes(content: &str, diags: &[LintDiag]) -> FixResult {
+    let mut accepted: Vec<OffsetEdit> = Vec::new();
+    let mut applied = 0;
+
+    for diag in diags {
+        let fix = match &diag.fix {
+            Some(fix) if !fix.is_empty() => fix,
+            _ => continue,
+        };
+        let edits: Option<Vec<OffsetEdit>> = fix.iter().map(|edit| resolve_edit(content, edit)).collect();
+        let edits = match edits {
+            Some(edits) => edits,
+            None => continue,
+        };
+        let conflict = edits.iter().enumerate().any(|(i, edit)| {
+            accepted.iter().any(|other| overlaps(edit, other))
+                || edits[i + 1..].iter().any(|other| overlaps(edit, other))
+        });
+        if conflict {
+            continue;
+        }
+        accepted.extend(edits);
+        applied += 1;
+    }
+
+    accepted.sort_by_key(|edit| std::cmp::Reverse(edit.start));
+    let mut res = content.to_string();
+    for edit in accepted {
+        res.replace_range(edit.start..edit.end, edit.new_text);
+    }
+    FixResult { content: res, applied }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Position, Range};
+
+    fn diag(content: &str, fix: Vec<TextEdit>) -> LintDiag {
+        LintDiag {
+            range: Range {
+                start: Position { line: 1, character: 0 },
+                end: Position { line: 1, character: 0 },
+                length: 0,
+            },
+            severity: None,
+            code: None,
+            source: None,
+            message: String::new(),
+            uri: String::new(),
+            source_file_content: content.to_string(),
+            fix: Some(fix),
+            suggestion: None,
+        }
+    }
+
+    fn edit(content: &str, offset: usize, length: usize, new_text: &str) -> TextEdit {
+        TextEdit {
+            range: range_from_offset(content, offset, length),
+            new_text: new_text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_apply_fixes() {
+        let content = "string a = 'foo';\nstring b = 'bar';\n";
+        let diags = vec![
+            diag(content, vec![edit(content, 11, 1, "\""), edit(content, 15, 1, "\"")]),
+            diag(content, vec![edit(content, 29, 1, "\""), edit(content, 33, 1, "\"")]),
+        ];
+        let res = apply_fixes(content, &diags);
+
+        assert_eq!(res.applied, 2);
+        assert_eq!(res.content, "string a = \"foo\";\nstring b = \"bar\";\n");
+    }
+
+    #[test]
+    fn test_apply_fixes_skips_overlapping() {
+        let content = "contract foo_bar {}";
+        let diags = vec![
+            diag(content, vec![edit(content, 9, 7, "FooBar")]),
+            diag(content, vec![edit(content, 12, 4, "Baz")]),
+        ];
+        let res = apply_fixes(content, &diags);
+
+        assert_eq!(res.applied, 1);
+        assert_eq!(res.content, "contract FooBar {}");
+    }
+
+    #[test]
+    fn test_replace_src() {
+        let content = "contract foo_bar {}";
+
+        assert!(replace_src(content, "9:7:0", "foo_bar", "FooBar").is_some());
+        assert!(replace_src(content, "9:7:0", "foo", "FooBar").is_none());
+        assert!(replace_src(content, "90:7:0", "foo_bar", "FooBar").is_none());
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use solc_wrapper::ImportResolver;
+use solc_wrapper::ast::ast::SourceUnitChildNodes;
+use crate::linter::SolidFile;
+
+/// An import directive of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Import {
+    /// The imported path, as written in the directive.
+    pub path: String,
+    /// The `src` location of the directive.
+    pub src: String,
+    /// The imported file, with the path it has among the linter files when it is one of them.
+    /// `None` when the file can't be found.
+    pub resolved: Option<String>,
+}
+
+/// The imports of the files of the linter, resolved the same way solc does.
+/// It is kept up to date file by file, as they are loaded, changed or removed.
+#[derive(Debug, Clone, Default)]
+pub struct ImportGraph {
+    imports: BTreeMap<String, Vec<Import>>,
+    // The path of each file of the graph, by canonical path
+    paths: HashMap<PathBuf, String>,
+}
+
+// The same file may be given relative or absolute, with `.` components...
+fn file_key(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+impl ImportGraph {
+    pub fn new(files: &[SolidFile], resolver: &ImportResolver) -> Self {
+        let mut graph = ImportGraph::default();
+        for file in files {
+            graph.update_file(file, resolver);
+        }
+        graph
+    }
+
+    /// Resolves the imports of `file` again, e.g. once it changed. The imports of the other files resolved to it
+    /// under another path get its path.
+    pub fn update_file(&mut self, file: &SolidFile, resolver: &ImportResolver) {
+        let key = file_key(Path::new(&file.path));
+        let is_new = !self.imports.contains_key(&file.path);
+        self.paths.insert(key.clone(), file.path.clone());
+
+        let imports = file.data.nodes.iter()
+            .filter_map(|node| match node {
+                SourceUnitChildNodes::ImportDirective(import) => Some(import),
+                _ => None,
+            })
+            .map(|import| {
+                let resolved = resolver.resolve(Path::new(&file.path), &import.file).map(|resolved| {
+                    match self.paths.get(&file_key(&resolved)) {
+                        Some(path) => path.clone(),
+                        None => resolved.to_string_lossy().to_string(),
+                    }
+                });
+                Import { path: import.file.clone(), src: import.src.clone(), resolved }
+            })
+            .collect();
+        self.imports.insert(file.path.clone(), imports);
+
+        if is_new {
+            // Only the imports of files that weren't in the graph may point to it
+            let aliases: HashSet<String> = self.missing_files().into_iter()
+                .filter(|missing| *missing != file.path && file_key(Path::new(missing)) == key)
+                .map(String::from)
+                .collect();
+            for import in self.imports.values_mut().flatten() {
+                if import.resolved.as_ref().is_some_and(|resolved| aliases.contains(resolved)) {
+                    import.resolved = Some(file.path.clone());
+                }
+            }
+        }
+    }
+
+    /// Removes the file at `path` from the graph. The imports of the other files still point to it.
+    pub fn remove_file(&mut self, path: &str) {
+        self.imports.remove(path);
+        self.paths.retain(|_, file| file != path);
+    }
+
+    /// The files of the graph, the ones whose imports were resolved.
+    pub fn files(&self) -> impl Iterator<Item = &str> {
+        self.imports.keys().map(String::as_str)
+    }
+
+    /// The imports of the file at `path`, in order.
+    pub fn imports(&self, path: &str) -> &[Import] {
+        self.imports.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// The resolved imports that aren't files of the graph yet.
+    pub fn missing_files(&self) -> Vec<&str> {
+        let mut missing: Vec<&str> = self.imports.values()
+            .flatten()
+            .filter_map(|import| import.resolved.as_deref())
+            .filter(|path| !self.imports.contains_key(*path))
+            .collect();
+        missing.sort();
+        missing.dedup();
+        missing
+    }
+
+    /// The files directly importing the file at `path`.
+    pub fn importers(&self, path: &str) -> Vec<&str> {
+        self.imports.iter()
+            .filter(|(_, imports)| imports.iter().any(|import| import.resolved.as_deref() == Some(path)))
+            .map(|(importer, _)| importer.as_str())
+            .collect()
+    }
+
+    /// The shortest chain of imports from the file at `from` to the one at `to`, both included.
+    pub fn import_chain<'a>(&'a self, from: &'a str, to: &str) -> Option<Vec<&'a str>> {
+        let mut previous: HashMap<&str, &str> = HashMap::new();
+        let mut pending = VecDeque::from([from]);
+        while let Some(file) = pending.pop_front() {
+            for imported in self.imports(file).iter().filter_map(|import| import.resolved.as_deref()) {
+                if imported == from || previous.contains_key(imported) {
+                    continue;
+                }
+                previous.insert(imported, file);
+                if imported == to {
+                    let mut chain = vec![imported];
+                    while let Some(file) = previous.get(chain[chain.len() - 1]) {
+                        chain.push(*file);
+                    }
+                    chain.reverse();
+                    return Some(chain);
+                }
+                pending.push_back(imported);
+            }
+        }
+        None
+    }
+
+    /// The files imported by the file at `path`, directly or through other files.
+    pub fn dependencies(&self, path: &str) -> Vec<&str> {
+        let mut dependencies: Vec<&str> = Vec::new();
+        let mut pending = vec![path];
+        while let Some(file) = pending.pop() {
+            for imported in self.imports(file).iter().filter_map(|import| import.resolved.as_deref()) {
+                if imported != path && !dependencies.contains(&imported) {
+                    dependencies.push(imported);
+                    pending.push(imported);
+                }
+            }
+        }
+        dependencies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+
+    fn file(path: &Path, imports: &[&str]) -> SolidFile {
+        let nodes: Vec<serde_json::Value> = imports.iter().enumerate().map(|(idx, import)| json!({
+            "id": idx + 1,
+            "src": format!("{}:10:0", idx * 10),
+            "nodeType": "ImportDirective",
+            "absolutePath": "",
+            "file": import,
+            "symbolAliases": [],
+            "unitAlias": "",
+        })).collect();
+        let data = serde_json::from_value(json!({
+            "id": 0,
+            "src": "0:0:0",
+            "nodeType": "SourceUnit",
+            "absolutePath": path,
+            "exportedSymbols": {},
+            "nodes": nodes,
+        })).unwrap();
+
+        SolidFile {
+            data,
+            path: path.to_string_lossy().to_string(),
+            content: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_import_graph() {
+        let root = Path::new("tests/files/imports");
+        let resolver = ImportResolver { roots: vec![root.to_path_buf()], remappings: vec!["@oz/=lib/oz/".to_string()] };
+        let vault = root.join("src/Vault.sol").to_string_lossy().to_string();
+        // Given with a `.` component, it is still the imported file
+        let token = root.join("src/./tokens/Token.sol").to_string_lossy().to_string();
+        let erc20 = root.join("lib/oz/ERC20.sol").to_string_lossy().to_string();
+        let mut files = vec![
+            file(Path::new(&vault), &["./tokens/Token.sol", "./Missing.sol"]),
+            file(Path::new(&token), &["@oz/ERC20.sol"]),
+        ];
+
+        let graph = ImportGraph::new(&files, &resolver);
+
+        assert_eq!(graph.imports(&vault)[0].resolved.as_deref(), Some(token.as_str()));
+        assert_eq!(graph.imports(&vault)[1], Import { path: "./Missing.sol".to_string(), src: "10:10:0".to_string(), resolved: None });
+        assert_eq!(graph.missing_files(), vec![erc20.as_str()]);
+        assert_eq!(graph.importers(&token), vec![vault.as_str()]);
+
+        let mut graph = graph;
+        files.push(file(Path::new(&erc20), &["../../src/Vault.sol"]));
+        graph.update_file(&files[2], &resolver);
+
+        assert!(graph.missing_files().is_empty());
+        assert_eq!(graph.dependencies(&vault), vec![token.as_str(), erc20.as_str()]);
+        assert_eq!(graph.import_chain(&token, &vault), Some(vec![token.as_str(), erc20.as_str(), vault.as_str()]));
+        assert_eq!(graph.import_chain(&vault, &root.join("src/Missing.sol").to_string_lossy()), None);
+        assert_eq!(graph.dependencies(&vault), ImportGraph::new(&files, &resolver).dependencies(&vault));
+
+        graph.remove_file(&erc20);
+        assert_eq!(graph.missing_files(), vec![erc20.as_str()]);
+    }
+}
@@ -0,0 +1,120 @@
+use solc_wrapper::ast::ast::SourceUnitChildNodes;
+use solc_wrapper::ast::index::{AstIndex, NodeRef};
+use crate::linter::SolidFile;
+
+/// Indexes the nodes of the loaded files.
+/// Files are compiled one by one, so node ids are only unique within a file: each file gets its own `AstIndex`.
+pub struct ProjectIndex<'a> {
+    files: Vec<(&'a SolidFile, AstIndex<'a>)>,
+}
+
+impl<'a> ProjectIndex<'a> {
+    pub fn new(files: &'a [SolidFile]) -> Self {
+        ProjectIndex {
+            files: files.iter().map(|file| (file, AstIndex::new(&file.data))).collect(),
+        }
+    }
+
+    pub fn file(&self, path: &str) -> Option<(&'a SolidFile, &AstIndex<'a>)> {
+        self.files.iter()
+            .find(|(file, _)| file.path == path)
+            .map(|(file, index)| (*file, index))
+    }
+
+    /// Resolves the declaration referenced by `node` of the file at `path`.
+    /// The `referencedDeclaration` id is looked up in the file itself, otherwise the referenced name
+    /// of an identifier or a type name is matched against the top-level declarations (contracts, structs, enums and errors) of the other files.
+    pub fn declaration(&self, path: &str, node: NodeRef<'a>) -> Option<(&'a SolidFile, NodeRef<'a>)> {
+        if let Some(declaration) = self.file(path).and_then(|(file, index)| Some((file, index.declaration(node)?))) {
+            return Some(declaration);
+        }
+
+        if !matches!(node, NodeRef::Identifier(_) | NodeRef::IdentifierPath(_) | NodeRef::UserDefinedTypeName(_)) {
+            return None;
+        }
+        let name = node.name()?.split('.').next()?;
+        self.files.iter()
+            .filter(|(file, _)| file.path != path)
+            .find_map(|(file, _)| {
+                file.data.nodes.iter().find_map(|child| {
+                    let declaration = match child {
+                        SourceUnitChildNodes::ContractDefinition(child) => NodeRef::from(&**child),
+                        SourceUnitChildNodes::StructDefinition(child) => NodeRef::from(&**child),
+                        SourceUnitChildNodes::EnumDefinition(child) => NodeRef::from(&**child),
+                        SourceUnitChildNodes::ErrorDefinition(child) => NodeRef::from(&**child),
+                        _ => return None,
+                    };
+                    (declaration.name() == Some(name)).then_some((*file, declaration))
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use solc_wrapper::ast::ast::BaseName;
+    use super::*;
+
+    fn contract(id: usize, name: &str, base_contracts: Value) -> Value {
+        json!({
+            "id": id,
+            "src": "0:0:0",
+            "nodeType": "ContractDefinition",
+            "name": name,
+            "abstract": false,
+            "baseContracts": base_contracts,
+            "contractDependencies": [],
+            "contractKind": "contract",
+            "fullyImplemented": true,
+            "linearizedBaseContracts": [id],
+            "nodes": [],
+            "scope": 0,
+            "usedErrors": [],
+        })
+    }
+
+    fn file(path: &str, contract: Value) -> SolidFile {
+        let data = serde_json::from_value(json!({
+            "id": 0,
+            "src": "0:0:0",
+            "nodeType": "SourceUnit",
+            "absolutePath": path,
+            "exportedSymbols": {},
+            "nodes": [contract],
+        })).unwrap();
+
+        SolidFile {
+            data,
+            path: path.to_string(),
+            content: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_project_index_declaration() {
+        let files = vec![
+            file("Token.sol", contract(1, "Token", json!([]))),
+            file("User.sol", contract(1, "User", json!([{
+                "id": 3,
+                "src": "0:0:0",
+                "nodeType": "InheritanceSpecifier",
+                "baseName": { "id": 2, "src": "0:0:0", "nodeType": "IdentifierPath", "name": "Token" },
+            }]))),
+        ];
+        let index = ProjectIndex::new(&files);
+        let (_, user_index) = index.file("User.sol").unwrap();
+        let base_name = user_index.get(2).unwrap();
+
+        assert!(matches!(user_index.parent(2), Some(NodeRef::InheritanceSpecifier(specifier))
+            if matches!(&specifier.base_name, BaseName::IdentifierPath(path) if path.name == "Token")));
+        match index.declaration("User.sol", base_name) {
+            Some((file, NodeRef::ContractDefinition(contract))) => {
+                assert_eq!(file.path, "Token.sol");
+                assert_eq!(contract.name, "Token");
+            }
+            declaration => panic!("unexpected declaration: {:?}", declaration.map(|(file, node)| (&file.path, node.id()))),
+        }
+        assert!(index.declaration("User.sol", user_index.get(1).unwrap()).is_none());
+    }
+}
@@ -1,9 +1,14 @@
-use crate::types::{Range};
-use solc_wrapper::ast::ast::{CodeLocation, offset_from_location};
+use crate::types::{Position, Range};
+use solc_wrapper::ast::ast::{CodeLocation, get_line_from_offset, offset_from_location};
 
 pub mod linter;
 pub mod types;
 pub mod rules;
+pub mod fix;
+pub mod suppression;
+pub mod index;
+pub mod imports;
+pub mod project;
 
 pub fn offset_from_range(content: &str, range: &Range) -> usize {
     let loc = CodeLocation {
@@ -14,10 +19,19 @@ pub fn offset_from_range(content: &str, range: &Range) -> usize {
     offset_from_location(content, &loc)
 }
 
+pub fn range_from_offset(content: &str, offset: usize, length: usize) -> Range {
+    let (start_line, start_character) = get_line_from_offset(content, offset);
+    let (end_line, end_character) = get_line_from_offset(content, offset + length);
+
+    Range {
+        start: Position { line: start_line as u64, character: start_character as u64 },
+        end: Position { line: end_line as u64, character: end_character as u64 },
+        length: length as u64,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn it_works() {
     }
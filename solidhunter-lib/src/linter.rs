@@ -1,11 +1,54 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
+use std::path::PathBuf;
 use crate::types::*;
 use crate::rules::types::*;
 use crate::rules::factory::RuleFactory;
-use crate::rules::rule_impl::{create_rules_file, parse_rules};
+use crate::rules::create_default_rules;
+use crate::rules::rule_impl::parse_rules;
+use crate::fix::{apply_fixes, FixReport};
+use crate::suppression::apply_suppressions;
+use crate::index::ProjectIndex;
+use crate::imports::ImportGraph;
+use crate::range_from_offset;
 
-use glob::glob;
-use solc_wrapper::{Solc, SourceUnit};
+use ignore::WalkBuilder;
+use ignore::overrides::{Override, OverrideBuilder};
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use solc_wrapper::{AstCache, Compilation, NodeType, ParsedSource, Solc, SolcError, SourceUnit};
+use solc_wrapper::standard_json::{CompilerError, ErrorSeverity};
+pub use solc_wrapper::{CacheConfig, CompileOptions, SolcConfig};
+pub use solc_wrapper::cache::DEFAULT_CACHE_DIR;
+use solc_wrapper::ast::visitor::visit::walk_source_unit;
+
+// Fixes can unlock new ones (e.g. once an import is moved), but never loop forever
+const MAX_FIX_PASSES: usize = 10;
+
+pub const IGNORE_FILE: &str = ".solidhunterignore";
+
+/// The `source` of the diagnostics reported by solc.
+pub const SOLC_DIAG_SOURCE: &str = "solc";
+
+// A file loaded by solc, along with the diagnostics solc reported on it
+struct LoadedFile {
+    ast: SourceUnit,
+    content: String,
+    solc_diags: Vec<LintDiag>,
+}
+
+type LoadResult = Result<LoadedFile, LintError>;
+
+/// How solc is run to get the AST of the linted files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AstMode {
+    /// Each file is only parsed: the AST has no types and references aren't resolved.
+    #[default]
+    Parse,
+    /// The files are compiled together up to the analysis, so rules can rely on types and references.
+    /// Sources that don't compile are linted on their parse-only AST.
+    Analyse,
+}
 
 pub struct SolidFile {
     pub data: SourceUnit,
@@ -16,39 +59,83 @@ pub struct SolidFile {
 pub struct SolidLinter {
     files: Vec<SolidFile>,
     rule_factory: RuleFactory,
-    rules : Vec<Box<dyn RuleType>>,
+    // Each rule along with its id, reported as the code of its diagnostics
+    rules : Vec<(String, Box<dyn RuleType>)>,
+    report_unused_suppressions: bool,
+    // Number of threads used to lint folders, 0 to use one per CPU
+    jobs: usize,
+    // Glob patterns of the paths to skip when walking a folder
+    excludes: Vec<String>,
+    use_gitignore: bool,
+    ast_mode: AstMode,
+    compile_options: CompileOptions,
+    solc: Solc,
+    solc_config: SolcConfig,
+    cache_config: Option<CacheConfig>,
+    import_graph: ImportGraph,
+    // Files only loaded because linted files import them
+    context_files: HashSet<String>,
+    // Imported files solc couldn't load, not tried again until the import options change
+    failed_imports: HashSet<String>,
+}
+
+impl Default for SolidLinter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SolidLinter {
-    fn _create_rules(&mut self, rules_config:& String, _first: bool)
+    fn _create_rules(&mut self, rules: Vec<RuleEntry>) -> Result<(), RulesError>
     {
-        let res = parse_rules(rules_config.as_str());
-        match res {
-            Ok(rules) => {
-                for rule in rules.rules {
-                    self.rules.push(self.rule_factory.create_rule(rule));
-                }
-            }
-            Err(_) => {
-                create_rules_file(rules_config.as_str());
-                if _first {
-                    self._create_rules(rules_config, false);
-                }
-            }
+        let mut created = Vec::new();
+        for rule in rules {
+            let id = rule.id.clone();
+            created.push((id, self.rule_factory.create_rule(rule)?));
+        }
+        self.rules = created;
+        Ok(())
+    }
+    /// Loads the rules of `rules_config`. The rules file is never created nor modified,
+    /// `RulesError::NotFound` is returned when it doesn't exist.
+    pub fn initalize(&mut self, rules_config: &str) -> Result<(), RulesError>
+    {
+        self.rule_factory.register_rules();
+        let rules = parse_rules(rules_config)?;
+        if let Some(compiler) = rules.compiler {
+            self.set_solc_config(SolcConfig {
+                offline: compiler.offline,
+                solc_path: compiler.solc.map(PathBuf::from),
+                default_version: compiler.default_version,
+            });
         }
+        self._create_rules(rules.rules)
     }
-    pub fn initalize(&mut self, rules_config: &String)
+    /// Loads the default rules, for when no rules file is available.
+    pub fn initalize_with_default_rules(&mut self)
     {
         self.rule_factory.register_rules();
-        self._create_rules(&rules_config, true);
+        self._create_rules(create_default_rules())
+            .expect("the default rules are valid");
     }
     pub fn new() -> SolidLinter {
-        let mut linter : SolidLinter = SolidLinter {
+        SolidLinter {
             files: Vec::new(),
             rule_factory: RuleFactory::new(),
             rules: Vec::new(),
-        };
-        return linter;
+            report_unused_suppressions: false,
+            jobs: 0,
+            excludes: Vec::new(),
+            use_gitignore: true,
+            ast_mode: AstMode::default(),
+            compile_options: CompileOptions::default(),
+            solc: Solc::default(),
+            solc_config: SolcConfig::default(),
+            cache_config: None,
+            import_graph: ImportGraph::default(),
+            context_files: HashSet::new(),
+            failed_imports: HashSet::new(),
+        }
     }
 
     fn file_exists(&self, path: &str) -> bool {
@@ -68,6 +155,14 @@ impl SolidLinter {
         }
     }
 
+    fn update_file_content(&mut self, path: &str, content: &str) {
+        for file in &mut self.files {
+            if file.path == path {
+                file.content = String::from(content);
+            }
+        }
+    }
+
     fn add_file(&mut self, path: &str, ast: SourceUnit, content: &str) {
         let file = SolidFile {
             data: ast,
@@ -77,72 +172,874 @@ impl SolidLinter {
         self.files.push(file);
     }
 
-    pub fn parse_file(&mut self, filepath: String) -> LintResult{
-        let res = Solc::default().extract_ast_file(filepath.clone());
-        
-        if res.is_err() {
-            println!("{:?}", res);
-            return Err(LintError::SolcError(res.err().unwrap()));
-        }
-        if self.file_exists(filepath.as_str()) {
-            self.update_file_ast(filepath.as_str(), res.expect("ast not found"));
-        } else {
-            let content = fs::read_to_string(filepath.clone()).map_err(|e| LintError::IoError(e))?;
-            self.add_file(filepath.as_str(), res.expect("ast not found"), content.as_str());
+    pub fn set_report_unused_suppressions(&mut self, report: bool) {
+        self.report_unused_suppressions = report;
+    }
+
+    pub fn set_jobs(&mut self, jobs: usize) {
+        self.jobs = jobs;
+    }
+
+    /// Sets the globs of the paths skipped when linting a folder, relative to the working directory.
+    pub fn set_excludes(&mut self, excludes: Vec<String>) -> Result<(), LintError> {
+        exclude_overrides(&excludes)?;
+        self.excludes = excludes;
+        Ok(())
+    }
+
+    pub fn set_use_gitignore(&mut self, use_gitignore: bool) {
+        self.use_gitignore = use_gitignore;
+    }
+
+    pub fn set_ast_mode(&mut self, ast_mode: AstMode) {
+        self.ast_mode = ast_mode;
+    }
+
+    /// Sets how solc resolves the imports, to analyse the files and to find the versions meeting the pragmas of their imports.
+    pub fn set_compile_options(&mut self, compile_options: CompileOptions) {
+        self.solc.set_import_options(&compile_options);
+        // The imports may resolve to other files
+        self.import_graph = ImportGraph::new(&self.files, &compile_options.import_resolver());
+        self.failed_imports.clear();
+        self.compile_options = compile_options;
+    }
+
+    /// Sets where the solc binaries come from, e.g. to only use the installed ones.
+    pub fn set_solc_config(&mut self, config: SolcConfig) {
+        self.solc = Solc::with_config(config.clone());
+        self.solc.set_cache(self.cache_config.clone().map(AstCache::new));
+        self.solc.set_import_options(&self.compile_options);
+        self.solc_config = config;
+    }
+
+    pub fn solc_config(&self) -> &SolcConfig {
+        &self.solc_config
+    }
+
+    /// Caches the ASTs of the parsed files on disk, so that the unchanged ones skip solc on the next runs.
+    /// Disabled by default.
+    pub fn set_cache_config(&mut self, config: Option<CacheConfig>) {
+        self.solc.set_cache(config.clone().map(AstCache::new));
+        self.cache_config = config;
+    }
+
+    /// The imports of the linted files and of the files they import.
+    pub fn import_graph(&self) -> &ImportGraph {
+        &self.import_graph
+    }
+
+    /// Whether the file at `path` was only loaded because a linted file imports it, such files aren't linted.
+    pub fn is_context_file(&self, path: &str) -> bool {
+        self.context_files.contains(path)
+    }
+
+    // Runs the project rules once over all the files, their diagnostics grouped by file
+    fn diagnose_project(&self) -> HashMap<String, Vec<LintDiag>> {
+        let project = ProjectFiles::new(&self.files, &self.import_graph, &self.context_files);
+        let mut res: HashMap<String, Vec<LintDiag>> = HashMap::new();
+
+        for (id, rule) in &self.rules {
+            if let Some(rule) = rule.project_rule() {
+                for mut diag in rule.diagnose_project(&project) {
+                    diag.code.get_or_insert_with(|| NumberOrString::String(id.clone()));
+                    res.entry(diag.uri.clone()).or_default().push(diag);
+                }
+            }
         }
+        res
+    }
+
+    // The project diagnostics of the file are added to the ones of its rules, before the suppressions apply
+    fn diagnose_file(&self, path: &str, mut project_diags: Vec<LintDiag>) -> Vec<LintDiag> {
         let mut res : Vec<LintDiag> = Vec::new();
 
-        for rule in &self.rules {
-            let mut diags = rule.diagnose(&self.files[0], &self.files);
-            res.append(&mut diags);
+        let file = match self.files.iter().find(|file| file.path == path) {
+            Some(file) => file,
+            None => return res,
+        };
+        let mut diags = Vec::new();
+        let mut visitors = Vec::new();
+        let mut visitor_ids = Vec::new();
+        for (id, rule) in &self.rules {
+            diags.push((id, rule.diagnose(file, &self.files)));
+            diags.push((id, rule.diagnose_imports(file, &self.files, &self.import_graph)));
+            if let Some(visitor) = rule.visitor(file) {
+                visitors.push(visitor);
+                visitor_ids.push(id);
+            }
+        }
+        // A single walk of the tree for all the rules inspecting nodes
+        walk_source_unit(visitors.as_mut_slice(), &file.data);
+        diags.extend(visitor_ids.into_iter().zip(visitors.into_iter().map(|visitor| visitor.into_diags())));
+
+        for (id, mut rule_diags) in diags {
+            for diag in &mut rule_diags {
+                diag.code.get_or_insert_with(|| NumberOrString::String(id.clone()));
+            }
+            res.append(&mut rule_diags);
         }
-        Ok(res)
+        res.append(&mut project_diags);
+        apply_suppressions(&file.content, &file.path, res, self.report_unused_suppressions)
     }
 
-    pub fn parse_content(&mut self, filepath: String, content : &String) -> LintResult {
-        let res = Solc::default().extract_ast_content(content.to_string());
+    // Runs solc on a file, without touching the linter state so it can be done concurrently
+    fn load_file(solc: &Solc, filepath: &str) -> LoadResult {
+        let content = fs::read_to_string(filepath).map_err(LintError::IoError)?;
+        let res = solc.extract_ast_file(filepath.to_string());
+        Self::loaded_file(filepath, content, res)
+    }
+
+    // A file solc fails to parse is still linted, with an empty AST, along with the errors of solc
+    fn loaded_file(filepath: &str, content: String, res: Result<ParsedSource, SolcError>) -> LoadResult {
+        match res {
+            Ok(parsed) => {
+                let solc_diags = solc_diags(&parsed.diagnostics, filepath, &content);
+                Ok(LoadedFile { ast: parsed.ast, content, solc_diags })
+            }
+            Err(SolcError::ParsingFailed(e)) => {
+                let solc_diags = solc_diags(&e.errors, filepath, &content);
+                Ok(LoadedFile { ast: empty_source_unit(filepath, &content), content, solc_diags })
+            }
+            Err(e) => Err(LintError::SolcError(e)),
+        }
+    }
+
+    // Gets the AST of `content` according to the AST mode, falling back to parsing it when it doesn't compile
+    fn load_content(&self, filepath: &str, content: &str) -> LoadResult {
+        let mut analysis_errors = None;
+        if self.ast_mode == AstMode::Analyse {
+            let sources = [(filepath.to_string(), content.to_string())];
+            match self.solc.compile_sources(&sources, &self.compile_options) {
+                Ok(mut compilation) => if let Some(ast) = compilation.asts.remove(filepath) {
+                    let solc_diags = solc_file_diags(&compilation.diagnostics, filepath, content);
+                    return Ok(LoadedFile { ast, content: content.to_string(), solc_diags });
+                },
+                Err(SolcError::CompilationFailed(errors)) => analysis_errors = Some(errors),
+                // solc couldn't be run, e.g. no version matches the pragma
+                Err(e) => return Err(LintError::SolcError(e)),
+            }
+        }
+        let res = self.solc.extract_ast_content(filepath, content.to_string());
+        let mut loaded = Self::loaded_file(filepath, content.to_string(), res)?;
+        // The diagnostics of the parser are also reported by the analysis
+        if let Some(errors) = analysis_errors {
+            loaded.solc_diags = solc_file_diags(&errors, filepath, content);
+        }
+        Ok(loaded)
+    }
 
-        if res.is_err() {
-            println!("{:?}", res);
-            return Err(LintError::SolcError(res.err().unwrap()));
+    // Analyses the files together, fails with the diagnostics of solc when they don't compile. Each file gets the error
+    // solc fails with otherwise.
+    fn analyse_files(&self, paths: &[String]) -> Result<Vec<(String, LoadResult)>, Vec<CompilerError>> {
+        let mut sources = Vec::new();
+        let mut loaded = Vec::new();
+        for path in paths {
+            match fs::read_to_string(path) {
+                Ok(content) => sources.push((path.clone(), content)),
+                Err(e) => loaded.push((path.clone(), Err(LintError::IoError(e)))),
+            }
+        }
+        let Compilation { mut asts, diagnostics } = match self.solc.compile_sources(&sources, &self.compile_options) {
+            Ok(compilation) => compilation,
+            Err(SolcError::CompilationFailed(errors)) => return Err(errors),
+            // solc couldn't be run, which parsing the files one by one wouldn't get around
+            Err(e) => {
+                let message = format!("Cannot analyse the files: {}", e);
+                loaded.extend(sources.into_iter().map(|(path, _)| (path, Err(LintError::LinterError(message.clone())))));
+                return Ok(loaded);
+            }
+        };
+        for (path, content) in sources {
+            let res = asts.remove(&path)
+                .map(|ast| {
+                    let solc_diags = solc_file_diags(&diagnostics, &path, &content);
+                    LoadedFile { ast, content, solc_diags }
+                })
+                .ok_or(LintError::SolcError(SolcError::OutputIsEmpty));
+            loaded.push((path, res));
         }
+        Ok(loaded)
+    }
 
-        if self.file_exists(filepath.as_str()) {
-            self.update_file_ast(filepath.as_str(), res.expect("ast not found"));
+    // Stores a loaded file, and resolves its imports
+    fn store_file(&mut self, path: &str, ast: SourceUnit, content: &str) {
+        self.context_files.remove(path);
+        self.failed_imports.remove(path);
+        if self.file_exists(path) {
+            self.update_file_ast(path, ast);
+            self.update_file_content(path, content);
         } else {
-            self.add_file(filepath.as_str(), res.expect("ast not found"), content.as_str());
+            self.add_file(path, ast, content);
         }
+        if let Some(file) = self.files.iter().find(|file| file.path == path) {
+            self.import_graph.update_file(file, &self.compile_options.import_resolver());
+        }
+    }
 
-        let mut res : Vec<LintDiag> = Vec::new();
+    // Loads the files imported by the loaded ones that aren't yet, when a rule looks at them, until every imported
+    // file is. Imported files are only loaded once, for the rules to look at them: they aren't linted.
+    fn load_imported_files(&mut self) {
+        if !self.rules.iter().any(|(_, rule)| rule.needs_imported_files()) {
+            return;
+        }
+        let mut pool = None;
+        loop {
+            let missing: Vec<String> = self.import_graph.missing_files().into_iter()
+                .filter(|path| !self.failed_imports.contains(*path))
+                .map(String::from)
+                .collect();
+            if missing.is_empty() {
+                return;
+            }
 
-        for rule in &self.rules {
-            let mut diags = rule.diagnose(&self.files[0], &self.files);
-            res.append(&mut diags);
+            let pool = match &mut pool {
+                Some(pool) => pool,
+                None => pool.insert(ThreadPoolBuilder::new().num_threads(self.jobs).build()),
+            };
+            let solc = &self.solc;
+            let load = |path: String| {
+                let res = Self::load_file(solc, path.as_str());
+                (path, res)
+            };
+            let loaded: Vec<(String, LoadResult)> = match pool {
+                Ok(pool) => pool.install(|| missing.into_par_iter().map(load).collect()),
+                Err(_) => missing.into_iter().map(load).collect(),
+            };
+            for (path, res) in loaded {
+                match res {
+                    Ok(loaded) => {
+                        self.store_file(path.as_str(), loaded.ast, loaded.content.as_str());
+                        self.context_files.insert(path);
+                    }
+                    Err(_) => {
+                        self.failed_imports.insert(path);
+                    }
+                }
+            }
         }
-        Ok(res)
     }
 
-    pub fn parse_folder(&mut self, folder: String) -> Vec<LintResult> {
-        let mut result: Vec<LintResult> = Vec::new();
+    // Stores a loaded file then lints it, the diagnostics of solc coming first
+    fn lint_loaded_file(&mut self, path: &str, loaded: LoadedFile) -> Vec<LintDiag> {
+        self.store_file(path, loaded.ast, loaded.content.as_str());
+        self.load_imported_files();
+        let project_diags = self.diagnose_project().remove(path).unwrap_or_default();
+        let mut diags = loaded.solc_diags;
+        diags.append(&mut self.diagnose_file(path, project_diags));
+        diags
+    }
 
-        for entry in glob(&*(folder + "/**/*.sol")) {
-            for path in entry {
-                result.push(self.parse_file(String::from(path.unwrap().into_os_string().into_string().unwrap())));
+    pub fn parse_file(&mut self, filepath: String) -> LintResult{
+        let loaded = match self.ast_mode {
+            AstMode::Parse => Self::load_file(&self.solc, filepath.as_str())?,
+            AstMode::Analyse => {
+                let content = fs::read_to_string(filepath.as_str()).map_err(LintError::IoError)?;
+                self.load_content(filepath.as_str(), content.as_str())?
+            }
+        };
+
+        Ok(self.lint_loaded_file(filepath.as_str(), loaded))
+    }
+
+    pub fn parse_content(&mut self, filepath: String, content : &str) -> LintResult {
+        let loaded = self.load_content(filepath.as_str(), content)?;
+
+        Ok(self.lint_loaded_file(filepath.as_str(), loaded))
+    }
+
+    pub fn parse_folder(&mut self, folder: String) -> BTreeMap<String, LintResult> {
+        self.parse_folders(&[folder])
+    }
+
+    /// Lints the files of the folders together, so that the project rules run once over all of them.
+    pub fn parse_folders(&mut self, folders: &[String]) -> BTreeMap<String, LintResult> {
+        let mut result: BTreeMap<String, LintResult> = BTreeMap::new();
+        let paths = self.folders_files(folders, &mut result);
+        let pool = match ThreadPoolBuilder::new().num_threads(self.jobs).build() {
+            Ok(pool) => pool,
+            Err(_) => {
+                for path in paths {
+                    let res = self.parse_file(path.clone());
+                    result.insert(path, res);
+                }
+                return result;
+            }
+        };
+
+        // Every file is loaded before linting any of them, rules can then look at the whole project
+        let loaded = self.load_files(&pool, paths);
+        result.append(&mut self.lint_loaded_files(&pool, loaded));
+        result
+    }
+
+    // The solidity files of the folders, a folder that can't be walked getting an error in `result`
+    fn folders_files<T>(&self, folders: &[String], result: &mut BTreeMap<String, Result<T, LintError>>) -> Vec<String> {
+        let mut paths = BTreeSet::new();
+        for folder in folders {
+            match get_sol_files(folder.clone(), &self.excludes, self.use_gitignore) {
+                Ok(files) => paths.extend(files),
+                Err(e) => {
+                    result.insert(folder.clone(), Err(e));
+                }
+            }
+        }
+        paths.into_iter().collect()
+    }
+
+    // Loads the files without storing them, analysed together in analysis mode
+    fn load_files(&self, pool: &ThreadPool, paths: Vec<String>) -> Vec<(String, LoadResult)> {
+        let analysed = match self.ast_mode {
+            AstMode::Analyse => self.analyse_files(&paths),
+            AstMode::Parse => Err(Vec::new()),
+        };
+        let solc = &self.solc;
+        let (loaded, analysis_errors) = match analysed {
+            Ok(loaded) => (loaded, Vec::new()),
+            Err(errors) => (pool.install(|| {
+                paths.into_par_iter().map(|path| {
+                    let res = Self::load_file(solc, path.as_str());
+                    (path, res)
+                }).collect()
+            }), errors),
+        };
+        loaded.into_iter()
+            .map(|(path, res)| {
+                let res = res.map(|mut loaded| {
+                    // The diagnostics of the parser are also reported by the analysis, which failed if it has any
+                    if !analysis_errors.is_empty() {
+                        loaded.solc_diags = solc_file_diags(&analysis_errors, &path, &loaded.content);
+                    }
+                    loaded
+                });
+                (path, res)
+            })
+            .collect()
+    }
+
+    // Stores the loaded files then lints them, once all of them are stored so that rules can look at the whole project
+    fn lint_loaded_files(&mut self, pool: &ThreadPool, loaded: Vec<(String, LoadResult)>) -> BTreeMap<String, LintResult> {
+        let mut result: BTreeMap<String, LintResult> = BTreeMap::new();
+        let mut loaded_paths = Vec::new();
+        let mut solc_diags = BTreeMap::new();
+        for (path, res) in loaded {
+            match res {
+                Ok(loaded) => {
+                    self.store_file(path.as_str(), loaded.ast, loaded.content.as_str());
+                    solc_diags.insert(path.clone(), loaded.solc_diags);
+                    loaded_paths.push(path);
+                }
+                Err(e) => {
+                    result.insert(path, Err(e));
+                }
             }
         }
+
+        self.load_imported_files();
+        let mut project_diags = self.diagnose_project();
+
+        let linter = &*self;
+        let files: Vec<(String, Vec<LintDiag>)> = loaded_paths.into_iter()
+            .map(|path| {
+                let diags = project_diags.remove(&path).unwrap_or_default();
+                (path, diags)
+            })
+            .collect();
+        let diags: Vec<(String, Vec<LintDiag>)> = pool.install(|| {
+            files.into_par_iter().map(|(path, project_diags)| {
+                let diags = linter.diagnose_file(path.as_str(), project_diags);
+                (path, diags)
+            }).collect()
+        });
+        for (path, mut diags) in diags {
+            let mut file_diags = solc_diags.remove(&path).unwrap_or_default();
+            file_diags.append(&mut diags);
+            result.insert(path, Ok(file_diags));
+        }
         result
     }
-    pub fn delete_file(&mut self, path: String) {
-        loop {
-            let idx = self.files.iter().position(|x| x.path == path);
-            match idx {
-                Some(idx) => {
-                    self.files.remove(idx);
+
+    pub fn fix_content(&mut self, filepath: String, content: &str) -> Result<FixReport, LintError> {
+        let mut content = content.to_string();
+        let mut diags = self.parse_content(filepath.clone(), &content)?;
+        let mut applied = 0;
+
+        // Fixes are only applied to code solc can parse
+        for _ in 0..MAX_FIX_PASSES {
+            if has_solc_errors(&diags) {
+                break;
+            }
+            let res = apply_fixes(&content, &diags);
+            if res.applied == 0 {
+                break;
+            }
+            match self.parse_content(filepath.clone(), &res.content) {
+                Ok(new_diags) if !has_solc_errors(&new_diags) => {
+                    content = res.content;
+                    diags = new_diags;
+                    applied += res.applied;
                 }
-                None => {
+                // A fix producing invalid code is dropped, keep the last valid content
+                _ => {
+                    self.parse_content(filepath.clone(), &content)?;
                     break;
                 }
             }
         }
+        Ok(FixReport { content, applied, diags })
+    }
+
+    pub fn fix_file(&mut self, filepath: String) -> Result<FixReport, LintError> {
+        let content = fs::read_to_string(filepath.clone()).map_err(LintError::IoError)?;
+        self.fix_content(filepath, &content)
+    }
+
+    pub fn fix_folder(&mut self, folder: String) -> BTreeMap<String, Result<FixReport, LintError>> {
+        self.fix_folders(&[folder])
+    }
+
+    /// Fixes the files of the folders one after the other. Every file is loaded first, so that the project rules
+    /// look at all of them whatever the file being fixed.
+    pub fn fix_folders(&mut self, folders: &[String]) -> BTreeMap<String, Result<FixReport, LintError>> {
+        let mut result = BTreeMap::new();
+        let paths = self.folders_files(folders, &mut result);
+
+        if let Ok(pool) = ThreadPoolBuilder::new().num_threads(self.jobs).build() {
+            for (path, res) in self.load_files(&pool, paths.clone()) {
+                if let Ok(loaded) = res {
+                    self.store_file(path.as_str(), loaded.ast, loaded.content.as_str());
+                }
+            }
+        }
+        for path in paths {
+            let res = self.fix_file(path.clone());
+            result.insert(path, res);
+        }
+        result
+    }
+
+    /// Unloads the file at `path`, along with the imported files no other linted file needs anymore.
+    /// A file other linted files still import is kept as an imported file instead, with its content on disk.
+    pub fn delete_file(&mut self, path: String) {
+        // The files the remaining linted files import
+        let needed: HashSet<String> = self.files.iter()
+            .filter(|file| file.path != path && !self.context_files.contains(&file.path))
+            .flat_map(|file| self.import_graph.dependencies(&file.path))
+            .map(String::from)
+            .collect();
+        match needed.contains(&path) {
+            true => self.demote_file(&path),
+            false => self.unload_file(&path),
+        }
+
+        // The context files only the deleted file imported
+        let unneeded: Vec<String> = self.context_files.difference(&needed).cloned().collect();
+        for path in unneeded {
+            self.unload_file(&path);
+        }
+    }
+
+    // Turns a linted file into a context file, reloaded from the disk when its content differs, e.g. once the changes
+    // of an editor are discarded
+    fn demote_file(&mut self, path: &str) {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(_) => {
+                self.unload_file(path);
+                return;
+            }
+        };
+        let is_unchanged = self.files.iter().any(|file| file.path == path && file.content == content);
+        if !is_unchanged {
+            match Self::load_file(&self.solc, path) {
+                Ok(loaded) => self.store_file(path, loaded.ast, loaded.content.as_str()),
+                Err(_) => {
+                    self.unload_file(path);
+                    return;
+                }
+            }
+        }
+        self.context_files.insert(path.to_string());
+    }
+
+    fn unload_file(&mut self, path: &str) {
+        self.files.retain(|file| file.path != path);
+        self.context_files.remove(path);
+        self.import_graph.remove_file(path);
+    }
+
+    /// Indexes the AST nodes of the loaded files, to resolve declarations across them.
+    pub fn index(&self) -> ProjectIndex<'_> {
+        ProjectIndex::new(&self.files)
+    }
+}
+
+// The AST of a file solc can't parse, so that the rules not relying on the AST still lint it
+pub(crate) fn empty_source_unit(path: &str, content: &str) -> SourceUnit {
+    SourceUnit {
+        id: 0,
+        src: format!("0:{}:0", content.len()),
+        absolute_path: path.to_string(),
+        exported_symbols: None,
+        license: None,
+        nodes: Vec::new(),
+        node_type: NodeType::SourceUnit,
+    }
+}
+
+// Source name given by solc to the standard input
+const STDIN_SOURCE: &str = "<stdin>";
+
+/// Converts the errors, warnings and infos of solc on `content` to diagnostics of the file at `path`.
+/// The ones located in another file, e.g. one it imports, are left out. The ones without location are put
+/// at the start of the file.
+pub fn solc_diags(errors: &[CompilerError], path: &str, content: &str) -> Vec<LintDiag> {
+    errors.iter().filter(|error| match &error.source_location {
+        Some(location) => is_same_source(&location.file, path) || location.file == STDIN_SOURCE,
+        None => true,
+    }).map(|error| {
+        let (start, end) = match &error.source_location {
+            Some(location) if location.start >= 0 && location.end >= location.start => (location.start as usize, location.end as usize),
+            _ => (0, 0),
+        };
+        LintDiag {
+            range: range_from_offset(content, start, end - start),
+            severity: Some(match error.severity {
+                ErrorSeverity::Error => Severity::ERROR,
+                ErrorSeverity::Warning => Severity::WARNING,
+                ErrorSeverity::Info => Severity::INFO,
+            }),
+            code: error.error_code.clone().map(NumberOrString::String),
+            source: Some(SOLC_DIAG_SOURCE.to_string()),
+            message: format!("{}: {}", error.error_type, error.message),
+            uri: path.to_string(),
+            source_file_content: content.to_string(),
+            fix: None,
+            suggestion: None,
+        }
+    }).collect()
+}
+
+// The diagnostics of a compilation located in the file at `path`
+fn solc_file_diags(errors: &[CompilerError], path: &str, content: &str) -> Vec<LintDiag> {
+    let errors: Vec<CompilerError> = errors.iter()
+        .filter(|error| error.source_location.as_ref().is_some_and(|location| is_same_source(&location.file, path)))
+        .cloned()
+        .collect();
+    solc_diags(&errors, path, content)
+}
+
+// Whether the source solc reports an error in is the file at `path`, solc normalizing e.g. `./src/A.sol` to `src/A.sol`
+fn is_same_source(source: &str, path: &str) -> bool {
+    fn normalize(path: &str) -> String {
+        let path = path.replace('\\', "/");
+        let mut path = path.as_str();
+        while let Some(rest) = path.strip_prefix("./") {
+            path = rest;
+        }
+        path.replace("/./", "/")
+    }
+    source == path || normalize(source) == normalize(path)
+}
+
+/// Whether solc reported errors among `diags`, e.g. because the file doesn't parse.
+pub fn has_solc_errors(diags: &[LintDiag]) -> bool {
+    diags.iter().any(|diag| diag.source.as_deref() == Some(SOLC_DIAG_SOURCE) && diag.severity == Some(Severity::ERROR))
+}
+
+// The exclude globs, anchored to the working directory like the paths given on the command line
+fn exclude_overrides(excludes: &[String]) -> Result<Override, LintError> {
+    let root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let mut overrides = OverrideBuilder::new(root);
+    for exclude in excludes {
+        // Override globs whitelist paths unless they are negated
+        overrides.add(&format!("!{}", exclude))
+            .map_err(|e| LintError::LinterError(format!("Invalid exclude pattern '{}': {}", exclude, e)))?;
+    }
+    overrides.build().map_err(|e| LintError::LinterError(format!("Invalid exclude patterns: {}", e)))
+}
+
+/// Lists the Solidity files of `folder`, skipping the paths matching one of the `excludes` globs,
+/// ignored by a `.solidhunterignore` file, or by git when `use_gitignore` is set.
+/// The globs are relative to the working directory, e.g. `lib/forge-std` or `**/mocks/*.sol`.
+pub fn get_sol_files(folder: String, excludes: &[String], use_gitignore: bool) -> Result<Vec<String>, LintError> {
+    let overrides = exclude_overrides(excludes)?;
+    let mut builder = WalkBuilder::new(&folder);
+    builder
+        .hidden(false)
+        .ignore(false)
+        .git_ignore(use_gitignore)
+        .git_global(use_gitignore)
+        .git_exclude(use_gitignore)
+        .require_git(false)
+        .add_custom_ignore_filename(IGNORE_FILE)
+        .overrides(overrides);
+
+    let mut files: Vec<String> = builder.build()
+        .flatten()
+        .filter(|entry| entry.file_type().is_some_and(|file_type| file_type.is_file()))
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "sol"))
+        .map(|entry| entry.path().to_string_lossy().to_string())
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_loaded_files_diagnoses_each_file() {
+        let mut linter = SolidLinter::new();
+        linter.rule_factory.register_rules();
+        for rule in create_default_rules().into_iter().filter(|rule| rule.id == "quotes") {
+            linter.rules.push((rule.id.clone(), linter.rule_factory.create_rule(rule).unwrap()));
+        }
+        let loaded = ["Clean", "Quoted"].iter()
+            .map(|name| {
+                let path = format!("tests/files/multi_file/{}.sol", name);
+                let ast = fs::read_to_string(format!("tests/files/multi_file/{}.json", name)).unwrap();
+                let loaded = LoadedFile {
+                    ast: serde_json::from_str(&ast).unwrap(),
+                    content: fs::read_to_string(&path).unwrap(),
+                    solc_diags: Vec::new(),
+                };
+                (path, Ok(loaded))
+            })
+            .collect();
+        let pool = ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+
+        let res = linter.lint_loaded_files(&pool, loaded);
+
+        assert_eq!(res.len(), 2);
+        let diags = |name: &str| res[&format!("tests/files/multi_file/{}.sol", name)].as_ref().unwrap();
+        assert!(diags("Clean").is_empty());
+        assert_eq!(diags("Quoted").len(), 2);
+        assert!(diags("Quoted").iter().all(|diag| diag.uri.ends_with("Quoted.sol")));
+    }
+
+    #[test]
+    #[ignore = "runs solc, which may have to be downloaded"]
+    fn test_parse_folder_diagnoses_each_file() {
+        let mut linter = SolidLinter::new();
+        linter.rule_factory.register_rules();
+        for rule in create_default_rules().into_iter().filter(|rule| rule.id == "quotes") {
+            linter.rules.push((rule.id.clone(), linter.rule_factory.create_rule(rule).unwrap()));
+        }
+
+        let res = linter.parse_folder("../solidhunter-lib/tests/files/multi_file".to_string());
+
+        assert_eq!(res.len(), 2);
+        for (path, diags) in &res {
+            let diags = diags.as_ref().expect("lint failed");
+            assert!(diags.iter().all(|diag| &diag.uri == path));
+            if path.ends_with("Clean.sol") {
+                assert_eq!(diags.len(), 0);
+            } else {
+                assert_eq!(diags.len(), 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_folders_files_merges_the_folders() {
+        let linter = SolidLinter::new();
+        let mut result: BTreeMap<String, LintResult> = BTreeMap::new();
+        let folders = ["tests/files/multi_file", "tests/files/quotes", "tests/files/multi_file"].map(String::from);
+
+        let paths: Vec<String> = linter.folders_files(&folders, &mut result).iter()
+            .map(|path| path.replace('\\', "/"))
+            .collect();
+
+        assert!(result.is_empty());
+        assert_eq!(paths, vec![
+            "tests/files/multi_file/Clean.sol",
+            "tests/files/multi_file/Quoted.sol",
+            "tests/files/quotes/Quotes.sol",
+        ]);
+    }
+
+    #[test]
+    fn test_lint_file_solc_fails_to_parse() {
+        let mut linter = SolidLinter::new();
+        linter.rule_factory.register_rules();
+        for rule in create_default_rules().into_iter().filter(|rule| rule.id == "quotes") {
+            linter.rules.push((rule.id.clone(), linter.rule_factory.create_rule(rule).unwrap()));
+        }
+        let content = "pragma solidity ^0.8.0;\n\ncontract Quoted {\n    string greeting = 'hello'\n}\n";
+        let error: CompilerError = serde_json::from_value(serde_json::json!({
+            "component": "general",
+            "message": "Expected ';' but got '}'",
+            "severity": "error",
+            "sourceLocation": { "file": "Quoted.sol", "start": 73, "end": 74 },
+            "type": "ParserError",
+        })).unwrap();
+        let res = Err(SolcError::ParsingFailed(solc_wrapper::ParsingError { errors: vec![error] }));
+
+        let loaded = SolidLinter::loaded_file("Quoted.sol", content.to_string(), res).unwrap();
+        let diags = linter.lint_loaded_file("Quoted.sol", loaded);
+
+        assert_eq!(diags.len(), 3);
+        assert_eq!(diags[0].source.as_deref(), Some(SOLC_DIAG_SOURCE));
+        assert_eq!(diags[0].message, "ParserError: Expected ';' but got '}'");
+        assert_eq!((diags[0].range.start.line, diags[0].range.start.character), (5, 0));
+        assert!(diags[1..].iter().all(|diag| diag.code == Some(NumberOrString::String("quotes".to_string()))));
+        assert!(has_solc_errors(&diags));
+    }
+
+    #[test]
+    fn test_solc_warnings_of_parsed_file() {
+        let content = "pragma solidity ^0.8.0;\n\ncontract Clean {}\n";
+        let warning = |file: &str| -> CompilerError {
+            serde_json::from_value(serde_json::json!({
+                "component": "general",
+                "errorCode": "1878",
+                "message": "SPDX license identifier not provided in source file.",
+                "severity": "warning",
+                "sourceLocation": { "file": file, "start": 25, "end": 42 },
+                "type": "Warning",
+            })).unwrap()
+        };
+        let ast = empty_source_unit("Clean.sol", content);
+        let res = Ok(ParsedSource { ast, diagnostics: vec![warning("<stdin>"), warning("lib/Imported.sol")] });
+
+        let loaded = SolidLinter::loaded_file("Clean.sol", content.to_string(), res).unwrap();
+
+        // The warning of the imported file isn't put on this one
+        assert_eq!(loaded.solc_diags.len(), 1);
+        assert_eq!(loaded.solc_diags[0].severity, Some(Severity::WARNING));
+        assert_eq!(loaded.solc_diags[0].code, Some(NumberOrString::String("1878".to_string())));
+        assert_eq!((loaded.solc_diags[0].range.start.line, loaded.solc_diags[0].range.start.character), (3, 0));
+        assert!(!has_solc_errors(&loaded.solc_diags));
+    }
+
+    #[test]
+    fn test_solc_diags_of_a_dot_relative_path() {
+        let content = "pragma solidity ^0.8.0;\n\ncontract Clean {}\n";
+        let error: CompilerError = serde_json::from_value(serde_json::json!({
+            "component": "general",
+            "errorCode": "1878",
+            "message": "SPDX license identifier not provided in source file.",
+            "severity": "warning",
+            "sourceLocation": { "file": "src/Clean.sol", "start": 25, "end": 42 },
+            "type": "Warning",
+        })).unwrap();
+
+        let diags = solc_diags(std::slice::from_ref(&error), "./src/Clean.sol", content);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].uri, "./src/Clean.sol");
+        assert_eq!(solc_file_diags(std::slice::from_ref(&error), "././src/Clean.sol", content).len(), 1);
+        assert!(solc_file_diags(&[error], "./lib/Clean.sol", content).is_empty());
+    }
+
+    #[test]
+    fn test_analysis_fails_without_solc() {
+        let mut linter = SolidLinter::new();
+        linter.set_ast_mode(AstMode::Analyse);
+        linter.set_solc_config(SolcConfig {
+            offline: true,
+            solc_path: Some(PathBuf::from("tests/files/missing_solc")),
+            default_version: None,
+        });
+
+        let res = linter.parse_content("Clean.sol".to_string(), "pragma solidity ^0.8.0;\n\ncontract Clean {}\n");
+        assert!(matches!(res, Err(LintError::SolcError(_))));
+        let paths = vec!["tests/files/multi_file/Clean.sol".to_string(), "tests/files/multi_file/Quoted.sol".to_string()];
+        let loaded = match linter.analyse_files(&paths) {
+            Ok(loaded) => loaded,
+            Err(_) => panic!("no compilation errors without solc"),
+        };
+        assert_eq!(loaded.len(), 2);
+        assert!(loaded.iter().all(|(_, res)| matches!(res, Err(LintError::LinterError(message)) if message.starts_with("Cannot analyse"))));
+    }
+
+    #[test]
+    fn test_delete_file_unloads_its_imports() {
+        let mut linter = SolidLinter::new();
+        let ast = |path: &str, import: &str| -> SourceUnit {
+            serde_json::from_value(serde_json::json!({
+                "id": 0,
+                "src": "0:0:0",
+                "nodeType": "SourceUnit",
+                "absolutePath": path,
+                "exportedSymbols": {},
+                "nodes": [{
+                    "id": 1,
+                    "src": "0:0:0",
+                    "nodeType": "ImportDirective",
+                    "absolutePath": "",
+                    "file": import,
+                    "symbolAliases": [],
+                    "unitAlias": "",
+                }],
+            })).unwrap()
+        };
+        let vault = "tests/files/imports/src/Vault.sol";
+        let token = "tests/files/imports/src/tokens/Token.sol";
+        linter.store_file(vault, ast(vault, "./tokens/Token.sol"), "");
+        linter.store_file(token, ast(token, "./Missing.sol"), "");
+        linter.context_files.insert(token.to_string());
+        assert_eq!(linter.import_graph().imports(vault)[0].resolved.as_deref(), Some(token));
+
+        linter.delete_file(vault.to_string());
+
+        assert!(linter.files.is_empty());
+        assert!(!linter.is_context_file(token));
+        assert_eq!(linter.import_graph().files().count(), 0);
+
+        // A closed file an open one imports is kept as an imported file
+        let content = fs::read_to_string(token).unwrap();
+        linter.store_file(vault, ast(vault, "./tokens/Token.sol"), "");
+        linter.store_file(token, ast(token, "./Missing.sol"), content.as_str());
+
+        linter.delete_file(token.to_string());
+
+        assert!(linter.is_context_file(token));
+        assert_eq!(linter.files.len(), 2);
+        linter.delete_file(vault.to_string());
+        assert!(linter.files.is_empty());
+        assert!(!linter.is_context_file(token));
+        assert_eq!(linter.import_graph().files().count(), 0);
+    }
+
+    #[test]
+    fn test_initalize_without_rules_file() {
+        let path = "tests/files/rules/none.json".to_string();
+        let mut linter = SolidLinter::new();
+
+        assert!(matches!(linter.initalize(&path), Err(RulesError::NotFound { .. })));
+        assert!(!std::path::Path::new(&path).exists());
+
+        linter.initalize_with_default_rules();
+        assert_eq!(linter.rules.len(), create_default_rules().len());
+    }
+
+    #[test]
+    fn test_get_sol_files_skips_ignored_paths() {
+        let folder = "tests/files/walk".to_string();
+        let relative = |files: Vec<String>| -> Vec<String> {
+            files.iter()
+                .map(|file| file.trim_start_matches(&folder).trim_start_matches(['/', '\\']).replace('\\', "/"))
+                .collect()
+        };
+
+        let files = relative(get_sol_files(folder.clone(), &["tests/files/walk/lib/forge-std".to_string()], true).unwrap());
+        assert_eq!(files, vec!["src/A.sol"]);
+
+        let files = relative(get_sol_files(folder.clone(), &[], false).unwrap());
+        assert_eq!(files, vec!["lib/forge-std/Test.sol", "src/A.sol", "src/Generated.sol"]);
+    }
+
+    #[test]
+    fn test_exclude_globs_are_relative_to_the_working_directory() {
+        let files = get_sol_files("tests/files".to_string(), &["tests/files/naming".to_string()], false).unwrap();
+        assert!(!files.is_empty());
+        assert!(files.iter().all(|file| !file.contains("naming")));
+
+        // Not a path of the working directory
+        let files = get_sol_files("tests/files".to_string(), &["naming/*.sol".to_string()], false).unwrap();
+        assert!(files.iter().any(|file| file.contains("naming")));
+
+        assert!(get_sol_files("tests/files".to_string(), &["naming/[".to_string()], false).is_err());
     }
 }
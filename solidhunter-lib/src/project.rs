@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use solc_wrapper::CompileOptions;
+use crate::types::LintError;
+
+pub const FOUNDRY_CONFIG: &str = "foundry.toml";
+pub const HARDHAT_CONFIGS: [&str; 4] = ["hardhat.config.js", "hardhat.config.ts", "hardhat.config.cjs", "hardhat.config.mjs"];
+pub const REMAPPINGS_FILE: &str = "remappings.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectKind {
+    Foundry,
+    Hardhat,
+}
+
+/// The layout of a Foundry or Hardhat project: where its sources are and how their imports are resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    pub kind: ProjectKind,
+    pub root: PathBuf,
+    /// Folders of the sources of the project, the ones linted by default.
+    pub source_dirs: Vec<PathBuf>,
+    /// Folders of the dependencies, where the imports are also looked up.
+    pub lib_dirs: Vec<PathBuf>,
+    /// Import remappings, as `[context:]prefix=target` with targets relative to the root.
+    pub remappings: Vec<String>,
+}
+
+// The settings of a `foundry.toml` profile used to find the sources and the imports
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct FoundryProfile {
+    src: Option<String>,
+    test: Option<String>,
+    script: Option<String>,
+    libs: Option<Vec<String>>,
+    remappings: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct FoundryConfig {
+    #[serde(default)]
+    profile: HashMap<String, FoundryProfile>,
+}
+
+impl Project {
+    /// Finds the project `path` belongs to, from a `foundry.toml` or a `hardhat.config.*` in it or one of its parents.
+    pub fn detect(path: &Path) -> Result<Option<Project>, LintError> {
+        let dir = match path.is_file() {
+            true => path.parent().unwrap_or(Path::new(".")),
+            false => path,
+        };
+        // The root is kept as given when possible, so that the linted paths stay relative
+        if let Some(project) = Self::from_root(dir)? {
+            return Ok(Some(project));
+        }
+        let dir = match dir.canonicalize() {
+            Ok(dir) => dir,
+            Err(_) => return Ok(None),
+        };
+        for ancestor in dir.ancestors().skip(1) {
+            if let Some(project) = Self::from_root(ancestor)? {
+                return Ok(Some(project));
+            }
+        }
+        Ok(None)
+    }
+
+    fn from_root(root: &Path) -> Result<Option<Project>, LintError> {
+        if root.join(FOUNDRY_CONFIG).is_file() {
+            return Self::foundry(root).map(Some);
+        }
+        if HARDHAT_CONFIGS.iter().any(|config| root.join(config).is_file()) {
+            return Ok(Some(Self::hardhat(root)));
+        }
+        Ok(None)
+    }
+
+    fn foundry(root: &Path) -> Result<Project, LintError> {
+        let config_path = root.join(FOUNDRY_CONFIG);
+        let content = fs::read_to_string(&config_path)?;
+        let mut config: FoundryConfig = toml::from_str(&content)
+            .map_err(|e| LintError::LinterError(format!("Invalid {}: {}", config_path.display(), e)))?;
+
+        // The settings of the selected profile fall back to the default profile ones
+        let default = config.profile.remove("default").unwrap_or_default();
+        let selected = std::env::var("FOUNDRY_PROFILE").ok()
+            .and_then(|name| config.profile.remove(&name))
+            .unwrap_or_default();
+        let src = selected.src.or(default.src).unwrap_or_else(|| "src".to_string());
+        let test = selected.test.or(default.test).unwrap_or_else(|| "test".to_string());
+        let script = selected.script.or(default.script).unwrap_or_else(|| "script".to_string());
+        let libs = selected.libs.or(default.libs).unwrap_or_else(|| vec!["lib".to_string()]);
+
+        let mut remappings = selected.remappings.or(default.remappings).unwrap_or_default();
+        remappings.extend(Self::remappings_file(root)?);
+        // Like forge, each dependency is remapped by its name unless the project does it
+        for lib in &libs {
+            for dependency in Self::lib_dependencies(&root.join(lib)) {
+                let prefix = format!("{}/", dependency);
+                if remappings.iter().any(|remapping| remapping.starts_with(&prefix)) {
+                    continue;
+                }
+                let target = match root.join(lib).join(&dependency).join("src").is_dir() {
+                    true => format!("{}/{}/src/", lib, dependency),
+                    false => format!("{}/{}/", lib, dependency),
+                };
+                remappings.push(format!("{}={}", prefix, target));
+            }
+        }
+
+        Ok(Project {
+            kind: ProjectKind::Foundry,
+            root: root.to_path_buf(),
+            source_dirs: [src, test, script].iter().map(|dir| root.join(dir)).collect(),
+            lib_dirs: libs.iter().map(|lib| root.join(lib)).collect(),
+            remappings,
+        })
+    }
+
+    fn hardhat(root: &Path) -> Project {
+        Project {
+            kind: ProjectKind::Hardhat,
+            root: root.to_path_buf(),
+            source_dirs: vec![root.join("contracts")],
+            lib_dirs: vec![root.join("node_modules")],
+            // Written by the hardhat-foundry plugin
+            remappings: Self::remappings_file(root).unwrap_or_default(),
+        }
+    }
+
+    fn remappings_file(root: &Path) -> Result<Vec<String>, LintError> {
+        let path = root.join(REMAPPINGS_FILE);
+        if !path.is_file() {
+            return Ok(Vec::new());
+        }
+        Ok(fs::read_to_string(path)?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(String::from)
+            .collect())
+    }
+
+    fn lib_dependencies(lib_dir: &Path) -> Vec<String> {
+        let mut dependencies: Vec<String> = fs::read_dir(lib_dir).into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        dependencies.sort();
+        dependencies
+    }
+
+    /// The source folders that exist, linted when no path is given.
+    pub fn lint_roots(&self) -> Vec<PathBuf> {
+        self.source_dirs.iter().filter(|dir| dir.is_dir()).cloned().collect()
+    }
+
+    /// Resolves the imports from the root, the dependencies and the remappings of the project.
+    pub fn compile_options(&self) -> CompileOptions {
+        CompileOptions {
+            base_path: Some(self.root.clone()),
+            include_paths: self.lib_dirs.iter().filter(|dir| dir.is_dir()).cloned().collect(),
+            remappings: self.remappings.clone(),
+            evm_version: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_foundry_project() {
+        let root = Path::new("tests/files/projects/foundry");
+
+        let project = Project::detect(&root.join("contracts/tokens")).unwrap().unwrap();
+
+        assert_eq!(project.kind, ProjectKind::Foundry);
+        assert_eq!(project.root, root.canonicalize().unwrap());
+        assert_eq!(project.lint_roots(), vec![project.root.join("contracts")]);
+        assert_eq!(project.remappings, vec![
+            "@solmate/=lib/solmate/src/",
+            "@openzeppelin/=lib/openzeppelin-contracts/",
+            "solmate/=lib/solmate/src/",
+            "forge-std/=lib/forge-std/src/",
+            "openzeppelin-contracts/=lib/openzeppelin-contracts/",
+        ]);
+        let options = project.compile_options();
+        assert_eq!(options.base_path, Some(project.root.clone()));
+        assert_eq!(options.include_paths, vec![project.root.join("lib")]);
+
+        assert!(Project::detect(Path::new("tests/files/projects/broken_foundry")).is_err());
+    }
+
+    #[test]
+    fn test_detect_hardhat_project() {
+        let root = Path::new("tests/files/projects/hardhat").canonicalize().unwrap();
+
+        let project = Project::detect(&root).unwrap().unwrap();
+
+        assert_eq!(project.kind, ProjectKind::Hardhat);
+        assert_eq!(project.root, root);
+        assert_eq!(project.lint_roots(), vec![root.join("contracts")]);
+        assert_eq!(project.compile_options().include_paths, vec![root.join("node_modules")]);
+        assert!(project.remappings.is_empty());
+
+        assert!(Project::detect(Path::new("tests/files/naming")).unwrap().is_none());
+    }
+}
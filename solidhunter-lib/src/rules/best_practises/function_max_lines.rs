@@ -1,7 +1,10 @@
 use crate::linter::SolidFile;
 use crate::rules::types::*;
+use crate::rules::reporter::RuleReporter;
 use crate::types::*;
 use solc_wrapper::ast::ast::*;
+use schemars::JsonSchema;
+use serde::{Serialize, Deserialize};
 
 // const DEFAULT_SEVERITY: &str = "warn";
 const DEFAULT_MESSAGE: &str = "Function contains too much lines";
@@ -9,6 +12,23 @@ const DEFAULT_MESSAGE: &str = "Function contains too much lines";
 // specific
 pub const DEFAULT_MAX_LINES: usize = 20;
 
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct FunctionMaxLinesOptions {
+    /// The maximum number of lines of a function body.
+    pub max_lines: usize,
+}
+
+impl Default for FunctionMaxLinesOptions {
+    fn default() -> Self {
+        FunctionMaxLinesOptions { max_lines: DEFAULT_MAX_LINES }
+    }
+}
+
+impl RuleOptions for FunctionMaxLinesOptions {
+    const POSITIONAL: &'static [&'static str] = &["maxLines"];
+}
+
 pub struct FunctionMaxLines {
     number_max_lines: usize,
     _data: RuleEntry
@@ -17,30 +37,22 @@ pub struct FunctionMaxLines {
 impl RuleType for FunctionMaxLines {
 
     fn diagnose(&self, _file: &SolidFile, _files: &Vec<SolidFile>) -> Vec<LintDiag> {
-        let mut res = Vec::new();
+        let mut reporter = RuleReporter::new(&self._data, _file);
 
         let functions = get_all_functions_from_ast(&_file.data.nodes);
         for function in functions {
             let _report = check_function_lines(_file, function, self.number_max_lines);
             if let Some(report) = _report {
-                res.push(LintDiag {
-                    range: report,
-                    severity: Some(Severity::WARNING),
-                    code: None,
-                    source: None,
-                    message: DEFAULT_MESSAGE.to_string(),
-                    uri: _file.path.clone(),
-                    source_file_content: _file.content.clone()
-                });
+                reporter.report(report, DEFAULT_MESSAGE);
             }
         }
-        res
+        reporter.into_diags()
     }
 }
 
 
 // returns a struct containing the line number of the start and end of the function if it is too long
-fn check_function_lines(_file: &SolidFile, function: Box<FunctionDefinition>, nb_max_line: usize) ->  Option<Range> {
+fn check_function_lines(_file: &SolidFile, function: &FunctionDefinition, nb_max_line: usize) ->  Option<Range> {
     let mut res: Option<Range> = None;
     let function_copy_name_location = &function.src;
     let (_start, _) = decode_location(function_copy_name_location.as_str(), _file.content.as_str());
@@ -81,7 +93,7 @@ fn check_function_lines(_file: &SolidFile, function: Box<FunctionDefinition>, nb
     res
 }
 
-fn get_all_functions_from_ast(ast_nodes: &Vec<SourceUnitChildNodes>) -> Vec<Box<FunctionDefinition>> {
+fn get_all_functions_from_ast(ast_nodes: &Vec<SourceUnitChildNodes>) -> Vec<&FunctionDefinition> {
     let mut res = Vec::new();
 
     for node in ast_nodes {
@@ -94,7 +106,7 @@ fn get_all_functions_from_ast(ast_nodes: &Vec<SourceUnitChildNodes>) -> Vec<Box<
                 ContractDefinitionChildNodes::FunctionDefinition(function) => function,
                 _ => continue,
             };
-            res.push(function.clone());
+            res.push(function.as_ref());
         }
     }
     res
@@ -105,24 +117,22 @@ impl FunctionMaxLines {
 
     pub(crate) const RULE_ID: &'static str = "function-max-lines";
 
-    pub fn create(data: RuleEntry) -> Box<dyn RuleType> {
-        let max_number_lines = match data.data[0].parse::<usize>() {
-            Ok(v) => v,
-            Err(_) => DEFAULT_MAX_LINES
-        };
+    pub fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        let max_number_lines = data.options::<FunctionMaxLinesOptions>()?.max_lines;
 
         let rule  = FunctionMaxLines {
             number_max_lines: max_number_lines,
             _data: data
         };
-        Box::new(rule)
+        Ok(Box::new(rule))
     }
     
     pub fn create_default() -> RuleEntry {
         RuleEntry {
             id: FunctionMaxLines::RULE_ID.to_string(),
             severity: Severity::WARNING,
-            data: vec![DEFAULT_MAX_LINES.to_string()]
+            data: vec![],
+            options: serde_json::to_value(FunctionMaxLinesOptions::default()).ok(),
             
         }
     }
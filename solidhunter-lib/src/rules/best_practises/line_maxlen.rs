@@ -1,9 +1,29 @@
-use std::ops::Index;
+use schemars::JsonSchema;
+use serde::{Serialize, Deserialize};
 use crate::linter::SolidFile;
-use solc_wrapper::*;
 use crate::rules::types::*;
+use crate::rules::reporter::RuleReporter;
 use crate::types::*;
 
+const DEFAULT_MAX_LEN: usize = 80;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct LineMaxLenOptions {
+    /// The maximum number of characters of a line.
+    pub max_length: usize,
+}
+
+impl Default for LineMaxLenOptions {
+    fn default() -> Self {
+        LineMaxLenOptions { max_length: DEFAULT_MAX_LEN }
+    }
+}
+
+impl RuleOptions for LineMaxLenOptions {
+    const POSITIONAL: &'static [&'static str] = &["maxLength"];
+}
+
 pub struct LineMaxLen {
     max_len: usize,
     data: RuleEntry
@@ -11,48 +31,39 @@ pub struct LineMaxLen {
 
 impl RuleType for LineMaxLen {
 
-    fn diagnose(&self, file: &SolidFile, files: &Vec<SolidFile>) -> Vec<LintDiag> {
-        let mut res = Vec::new();
-        let mut line_idx = 1;
-
-        for line in file.content.lines() {
+    fn diagnose(&self, file: &SolidFile, _files: &Vec<SolidFile>) -> Vec<LintDiag> {
+        let mut reporter = RuleReporter::new(&self.data, file);
+        for (line_idx, line) in (1..).zip(file.content.lines()) {
             if line.len() > self.max_len {
-                res.push(LintDiag {
-                    range: Range {
-                        start: Position { line: line_idx, character: self.max_len as u64},
-                        end: Position { line: line_idx, character: line.len() as u64 },
-                        length: (line.len() - self.max_len) as u64
-                    },
-                    message: format!("Line is too long: {}", line.len()),
-                    severity: Some(self.data.severity),
-                    code: None,
-                    source: None,
-                    uri: file.path.clone(),
-                    source_file_content: file.content.clone()
-                });
+                let range = Range {
+                    start: Position { line: line_idx, character: self.max_len as u64},
+                    end: Position { line: line_idx, character: line.len() as u64 },
+                    length: (line.len() - self.max_len) as u64
+                };
+                reporter.report(range, format!("Line is too long: {}", line.len()));
             }
-            line_idx += 1;
         }
-        res
+        reporter.into_diags()
     }
 
 
 }
 
 impl LineMaxLen {
-    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
-        let mut rule  = LineMaxLen {
-            max_len: data.data[0].parse::<usize>().unwrap(),
+    pub(crate) fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        let rule  = LineMaxLen {
+            max_len: data.options::<LineMaxLenOptions>()?.max_length,
             data
         };
-        Box::new(rule)
+        Ok(Box::new(rule))
     }
 
     pub(crate) fn create_default() -> RuleEntry {
         RuleEntry {
             id: "line-max-len".to_string(),
             severity: Severity::WARNING,
-            data: vec!["80".to_string()]
+            data: vec![],
+            options: serde_json::to_value(LineMaxLenOptions::default()).ok(),
         }
     }
 }
\ No newline at end of file
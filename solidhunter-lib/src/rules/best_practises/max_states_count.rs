@@ -1,9 +1,29 @@
-use std::ops::Index;
+use schemars::JsonSchema;
+use serde::{Serialize, Deserialize};
 use crate::linter::SolidFile;
 use solc_wrapper::*;
 use crate::rules::types::*;
+use crate::rules::reporter::RuleReporter;
 use crate::types::*;
 
+const DEFAULT_MAX_STATES: usize = 15;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct MaxStatesCountOptions {
+    /// The maximum number of state variables declared in the file.
+    pub max_states: usize,
+}
+
+impl Default for MaxStatesCountOptions {
+    fn default() -> Self {
+        MaxStatesCountOptions { max_states: DEFAULT_MAX_STATES }
+    }
+}
+
+impl RuleOptions for MaxStatesCountOptions {
+    const POSITIONAL: &'static [&'static str] = &["maxStates"];
+}
 
 pub struct MaxStatesCount {
     max_states: usize,
@@ -12,8 +32,8 @@ pub struct MaxStatesCount {
 
 impl RuleType for MaxStatesCount {
 
-    fn diagnose(&self, file: &SolidFile, files: &Vec<SolidFile>) -> Vec<LintDiag> {
-        let mut res = Vec::new();
+    fn diagnose(&self, file: &SolidFile, _files: &Vec<SolidFile>) -> Vec<LintDiag> {
+        let mut reporter = RuleReporter::new(&self.data, file);
 
         let mut count = 0;
 
@@ -30,24 +50,11 @@ impl RuleType for MaxStatesCount {
                 };
                 count += 1;
                 if count > self.max_states {
-                    let location = decode_location(&var.src, &file.content);
-                    res.push(LintDiag {
-                        range: Range {
-                            start: Position { line: location.0.line as u64, character: location.0.column as u64},
-                            end: Position { line: location.1.line as u64, character: location.1.column as u64 },
-                            length: location.0.length as u64
-                        },
-                        message: format!("Too many states: {}", count),
-                        severity: Some(self.data.severity),
-                        code: None,
-                        source: None,
-                        uri: file.path.clone(),
-                        source_file_content: file.content.clone()
-                    });
+                    reporter.report_src(&var.src, format!("Too many states: {}", count));
                 }
             }
         }
-        res
+        reporter.into_diags()
     }
 }
 
@@ -55,19 +62,20 @@ impl MaxStatesCount {
 
     pub(crate) const RULE_ID: &'static str = "max-states-count";
 
-    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
-        let mut rule  = MaxStatesCount {
-            max_states: data.data[0].parse::<usize>().unwrap(),
+    pub(crate) fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        let rule  = MaxStatesCount {
+            max_states: data.options::<MaxStatesCountOptions>()?.max_states,
             data
         };
-        Box::new(rule)
+        Ok(Box::new(rule))
     }
 
     pub(crate) fn create_default() -> RuleEntry {
         RuleEntry {
             id: MaxStatesCount::RULE_ID.to_string(),
             severity: Severity::WARNING,
-            data: vec!["15".to_string()]
+            data: vec![],
+            options: serde_json::to_value(MaxStatesCountOptions::default()).ok(),
         }
     }
 }
\ No newline at end of file
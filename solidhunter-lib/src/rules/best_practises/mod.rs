@@ -1,5 +1,6 @@
 use std::collections::HashMap;
-use crate::rules::types::{RuleEntry, RuleType};
+use schemars::gen::SchemaGenerator;
+use crate::rules::types::RuleEntry;
 
 #[macro_use]
 pub mod line_maxlen;
@@ -9,24 +10,22 @@ pub mod reason_string;
 
 // List all rules
 
-use crate::rules::best_practises::line_maxlen::LineMaxLen;
-use crate::rules::best_practises::max_states_count::MaxStatesCount;
-use crate::rules::best_practises::reason_string::ReasonString;
-use crate::rules::best_practises::function_max_lines::FunctionMaxLines;
-use crate::rules::RuleBuilder;
+use crate::rules::best_practises::line_maxlen::{LineMaxLen, LineMaxLenOptions};
+use crate::rules::best_practises::max_states_count::{MaxStatesCount, MaxStatesCountOptions};
+use crate::rules::best_practises::reason_string::{ReasonString, ReasonStringOptions};
+use crate::rules::best_practises::function_max_lines::{FunctionMaxLines, FunctionMaxLinesOptions};
+use crate::rules::{OptionsSchemaBuilder, RuleBuilder};
 
 pub fn create_default_rules() -> Vec<RuleEntry> {
-    let mut rules = Vec::new();
-
-    rules.push(LineMaxLen::create_default());
-    rules.push(MaxStatesCount::create_default());
-    rules.push(FunctionMaxLines::create_default());
-    rules.push(ReasonString::create_default());
-
-    rules
+    vec![
+        LineMaxLen::create_default(),
+        MaxStatesCount::create_default(),
+        FunctionMaxLines::create_default(),
+        ReasonString::create_default(),
+    ]
 }
 
-pub fn create_rules() -> HashMap<String, fn(RuleEntry) -> Box<dyn RuleType>> {
+pub fn create_rules() -> HashMap<String, RuleBuilder> {
     let mut rules :  HashMap<String, RuleBuilder> = HashMap::new();
 
     rules.insert( "line-max-len".to_string(), LineMaxLen::create);
@@ -35,4 +34,15 @@ pub fn create_rules() -> HashMap<String, fn(RuleEntry) -> Box<dyn RuleType>> {
     rules.insert(reason_string::RULE_ID.to_string(), ReasonString::create);
 
     rules
-}
\ No newline at end of file
+}
+
+pub fn create_options_schemas() -> HashMap<String, OptionsSchemaBuilder> {
+    let mut schemas: HashMap<String, OptionsSchemaBuilder> = HashMap::new();
+
+    schemas.insert("line-max-len".to_string(), SchemaGenerator::subschema_for::<LineMaxLenOptions>);
+    schemas.insert(MaxStatesCount::RULE_ID.to_string(), SchemaGenerator::subschema_for::<MaxStatesCountOptions>);
+    schemas.insert(FunctionMaxLines::RULE_ID.to_string(), SchemaGenerator::subschema_for::<FunctionMaxLinesOptions>);
+    schemas.insert(reason_string::RULE_ID.to_string(), SchemaGenerator::subschema_for::<ReasonStringOptions>);
+
+    schemas
+}
@@ -1,9 +1,12 @@
-use solc_wrapper::{NodeType, Expression, decode_location};
-use solc_wrapper::ast::utils::{get_all_nodes_by_type, self};
+use solc_wrapper::{Expression, FunctionCall};
+use solc_wrapper::ast::visitor::Visitor;
+use schemars::JsonSchema;
+use serde::{Serialize, Deserialize};
 
 use crate::linter::SolidFile;
-use crate::rules::types::{RuleEntry, RuleType};
-use crate::types::{LintDiag, Range, Position, Severity};
+use crate::rules::reporter::RuleReporter;
+use crate::rules::types::{RuleEntry, RuleOptions, RuleType, RuleVisitor, RulesError};
+use crate::types::{LintDiag, Severity};
 
 pub const RULE_ID: &str = "reason-string";
 const DEFAULT_SEVERITY: Severity = Severity::WARNING;
@@ -11,134 +14,96 @@ const DEFAULT_SEVERITY: Severity = Severity::WARNING;
 // Specific
 const DEFAULT_LENGTH: u32 = 32;
 
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct ReasonStringOptions {
+    /// The maximum length of a revert or require reason string.
+    pub max_length: u32,
+}
+
+impl Default for ReasonStringOptions {
+    fn default() -> Self {
+        ReasonStringOptions { max_length: DEFAULT_LENGTH }
+    }
+}
+
+impl RuleOptions for ReasonStringOptions {
+    const POSITIONAL: &'static [&'static str] = &["maxLength"];
+}
+
 
 pub struct ReasonString {
     max_length: u32,
     data: RuleEntry
 }
 
-impl RuleType for ReasonString {
+struct ReasonStringVisitor<'a> {
+    max_length: u32,
+    reporter: RuleReporter<'a>,
+}
 
-    fn diagnose(&self, file: &SolidFile, files: &Vec<SolidFile>) -> Vec<LintDiag> {
-        let mut res = Vec::new();
-
-        let nodes = get_all_nodes_by_type(file.data.clone(), NodeType::FunctionCall);
-        for i in &nodes {
-            match i {
-                utils::Nodes::FunctionCall(j) => {
-                    match &j.expression {
-                        Expression::Identifier(v) => {
-                            if v.name == "require" {
-                                if j.arguments.len() != 2 {
-                                    let location = decode_location(&j.src, &file.content);
-                                    let diag = LintDiag {
-                                        range: Range {
-                                            start: Position { line: location.0.line as u64, character: location.0.column as u64 },
-                                            end: Position { line: location.1.line as u64, character: location.1.column as u64 },
-                                            length: location.0.length as u64
-                                        },
-                                        message: format!("reason-string: A require statement must have a reason string"),
-                                        severity: Some(self.data.severity),
-                                        code: None,
-                                        source: None,
-                                        uri: file.path.clone(),
-                                        source_file_content: file.content.clone(),
-                                    };
-                                    res.push(diag);
-                                } else {
-                                    for nj in &j.arguments {
-                                        match nj {
-                                            Expression::Literal(z) => {
-                                                if z.value.clone().unwrap().len() > self.max_length as usize {
-                                                    let location = decode_location(&z.src, &file.content);
-                                                    let diag = LintDiag {
-                                                        range: Range {
-                                                            start: Position { line: location.0.line as u64, character: location.0.column as u64 },
-                                                            end: Position { line: location.1.line as u64, character: location.1.column as u64 },
-                                                            length: location.0.length as u64
-                                                        },
-                                                        message: format!("reason-string: A revert statement must have a reason string of length less than {}", self.max_length),
-                                                        severity: Some(self.data.severity),
-                                                        code: None,
-                                                        source: None,
-                                                        uri: file.path.clone(),
-                                                        source_file_content: file.content.clone(),
-                                                    };
-                                                    res.push(diag);
-                                                }
-                                            }
-                                            _ => {}
-                                        }
-                                    }
-                                }
-                            } else if v.name == "revert" {
-                                if j.arguments.len() == 0 {
-                                    let location = decode_location(&j.src, &file.content);
-                                    let diag = LintDiag {
-                                        range: Range {
-                                            start: Position { line: location.0.line as u64, character: location.0.column as u64 },
-                                            end: Position { line: location.1.line as u64, character: location.1.column as u64 },
-                                            length: location.0.length as u64
-                                        },
-                                        message: format!("reason-string: A revert statement must have a reason string"),
-                                        severity: Some(self.data.severity),
-                                        code: None,
-                                        source: None,
-                                        uri: file.path.clone(),
-                                        source_file_content: file.content.clone(),
-                                    };
-                                    res.push(diag);
-                                } else {
-                                    match &j.arguments[0] {
-                                        Expression::Literal(z) => {
-                                            if z.value.clone().unwrap().len() > self.max_length as usize {
-                                                let location = decode_location(&z.src, &file.content);
-                                                let diag = LintDiag {
-                                                    range: Range {
-                                                        start: Position { line: location.0.line as u64, character: location.0.column as u64 },
-                                                        end: Position { line: location.1.line as u64, character: location.1.column as u64 },
-                                                        length: location.0.length as u64
-                                                    },
-                                                    message: format!("reason-string: A revert statement must have a reason string of length less than {}", self.max_length),
-                                                    severity: Some(self.data.severity),
-                                                    code: None,
-                                                    source: None,
-                                                    uri: file.path.clone(),
-                                                    source_file_content: file.content.clone(),
-                                                };
-                                                res.push(diag);
-                                            }
-                                        }
-                                        _ => {}
-                                    }
-                                }
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-                _ => {}
+impl ReasonStringVisitor<'_> {
+    fn check_reason(&mut self, reason: &Expression) {
+        if let Expression::Literal(literal) = reason {
+            if literal.value.as_ref().map_or(0, String::len) > self.max_length as usize {
+                self.reporter.report_src(&literal.src, format!("reason-string: A revert statement must have a reason string of length less than {}", self.max_length));
             }
         }
-        res
+    }
+}
+
+impl Visitor for ReasonStringVisitor<'_> {
+    fn visit_function_call(&mut self, node: &FunctionCall) {
+        let name = match &node.expression {
+            Expression::Identifier(identifier) => identifier.name.as_str(),
+            _ => return,
+        };
+        match (name, node.arguments.as_slice()) {
+            ("require", [_, reason]) => self.check_reason(reason),
+            ("require", _) => {
+                self.reporter.report_src(&node.src, "reason-string: A require statement must have a reason string");
+            }
+            ("revert", []) => {
+                self.reporter.report_src(&node.src, "reason-string: A revert statement must have a reason string");
+            }
+            ("revert", [reason, ..]) => self.check_reason(reason),
+            _ => {}
+        }
+    }
+}
+
+impl RuleVisitor for ReasonStringVisitor<'_> {
+    fn into_diags(self: Box<Self>) -> Vec<LintDiag> {
+        self.reporter.into_diags()
+    }
+}
+
+impl RuleType for ReasonString {
+
+    fn visitor<'a>(&'a self, file: &'a SolidFile) -> Option<Box<dyn RuleVisitor + 'a>> {
+        Some(Box::new(ReasonStringVisitor {
+            max_length: self.max_length,
+            reporter: RuleReporter::new(&self.data, file),
+        }))
     }
 
 }
 
 impl ReasonString {
-    pub fn create(data: RuleEntry) -> Box<dyn RuleType> {
-        let mut rule  = ReasonString {
-            max_length: data.data[0].parse::<u32>().unwrap(),
+    pub fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        let rule  = ReasonString {
+            max_length: data.options::<ReasonStringOptions>()?.max_length,
             data
         };
-        Box::new(rule)
+        Ok(Box::new(rule))
     }
 
     pub fn create_default() -> RuleEntry {
         RuleEntry {
             id: RULE_ID.to_string(),
             severity: DEFAULT_SEVERITY,
-            data: vec![DEFAULT_LENGTH.to_string()]
+            data: vec![],
+            options: serde_json::to_value(ReasonStringOptions::default()).ok(),
         }
     }
 }
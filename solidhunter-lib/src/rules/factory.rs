@@ -1,12 +1,21 @@
 use std::collections::HashMap;
 use crate::rules::types::*;
-use crate::rules::create_rules;
+use crate::rules::{create_rules, RuleBuilder};
+
+// Rule ids further than this from an unknown id are not worth suggesting
+const MAX_SUGGESTION_DISTANCE: usize = 3;
 
 pub struct RuleFactory {
-    _buildables: HashMap<String, fn(RuleEntry) -> Box<dyn RuleType>>,
+    _buildables: HashMap<String, RuleBuilder>,
     _rules : Vec<Box<dyn RuleType>>,
 }
 
+impl Default for RuleFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RuleFactory {
     pub fn new() -> RuleFactory {
         RuleFactory {
@@ -20,12 +29,97 @@ impl RuleFactory {
         self._buildables = create_rules()
     }
     
-    pub fn create_rule(&self, rule: RuleEntry) -> Box<dyn RuleType>
+    pub fn create_rule(&self, rule: RuleEntry) -> Result<Box<dyn RuleType>, RulesError>
+    {
+        match self._buildables.get(&rule.id) {
+            Some(builder) => builder(rule),
+            None => Err(RulesError::UnknownRule {
+                suggestion: self.suggest_rule_id(&rule.id),
+                id: rule.id,
+            }),
+        }
+    }
+
+    // Finds the registered rule id closest to `id`, if any is close enough
+    fn suggest_rule_id(&self, id: &str) -> Option<String>
     {
-        let rule_type = self._buildables.get(&rule.id);
-        if rule_type.is_none() {
-            panic!("Rule {} not found", &rule.id);
+        self._buildables.keys()
+            .map(|known| (strsim::levenshtein(id, known), known))
+            .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+            .min()
+            .map(|(_, known)| known.clone())
+    }
+
+    pub fn get_rule_ids(&self) -> Vec<String>
+    {
+        let mut ids: Vec<String> = self._buildables.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Severity;
+    use crate::rules::create_default_rules;
+
+    fn entry(id: &str, data: Vec<&str>) -> RuleEntry {
+        RuleEntry {
+            id: id.to_string(),
+            severity: Severity::WARNING,
+            data: data.into_iter().map(String::from).collect(),
+            options: None,
         }
-        rule_type.unwrap()(rule)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_create_unknown_rule() {
+        let mut factory = RuleFactory::new();
+        factory.register_rules();
+
+        match factory.create_rule(entry("quote", vec![])) {
+            Err(RulesError::UnknownRule { suggestion, .. }) => assert_eq!(suggestion, Some("quotes".to_string())),
+            _ => panic!("expected an unknown rule error"),
+        }
+        match factory.create_rule(entry("no-such-rule-at-all", vec![])) {
+            Err(RulesError::UnknownRule { suggestion, .. }) => assert_eq!(suggestion, None),
+            _ => panic!("expected an unknown rule error"),
+        }
+    }
+
+    #[test]
+    fn test_create_rule_invalid_options() {
+        let mut factory = RuleFactory::new();
+        factory.register_rules();
+
+        assert!(factory.create_rule(entry("line-max-len", vec!["120"])).is_ok());
+        assert!(factory.create_rule(entry("line-max-len", vec![])).is_ok());
+        assert!(matches!(
+            factory.create_rule(entry("line-max-len", vec!["eighty"])),
+            Err(RulesError::InvalidRuleOptions { .. })
+        ));
+    }
+
+    #[test]
+    fn test_create_project_rule() {
+        let mut factory = RuleFactory::new();
+        factory.register_rules();
+
+        assert!(factory.get_rule_ids().contains(&"no-circular-imports".to_string()));
+        assert!(factory.create_rule(entry("no-circular-imports", vec![])).unwrap().project_rule().is_some());
+        assert!(factory.create_rule(entry("quotes", vec![])).unwrap().project_rule().is_none());
+    }
+
+    #[test]
+    fn test_default_rules_do_not_load_imported_files() {
+        let mut factory = RuleFactory::new();
+        factory.register_rules();
+
+        // Loading the imported files runs solc on each of them
+        for rule in create_default_rules() {
+            assert!(!factory.create_rule(rule).unwrap().needs_imported_files());
+        }
+        assert!(factory.create_rule(entry("no-circular-imports", vec![])).unwrap().needs_imported_files());
+    }
+}
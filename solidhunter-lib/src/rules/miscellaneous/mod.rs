@@ -1,26 +1,28 @@
 use std::collections::HashMap;
-use crate::rules::types::{RuleEntry, RuleType};
+use crate::rules::types::RuleEntry;
 
 #[macro_use]
 pub mod quotes;
+pub mod no_unresolved_import;
 
 // List all rules
 
 use crate::rules::miscellaneous::quotes::Quotes;
+use crate::rules::miscellaneous::no_unresolved_import::NoUnresolvedImport;
 use crate::rules::RuleBuilder;
 
 pub fn create_default_rules() -> Vec<RuleEntry> {
-    let mut rules = Vec::new();
-
-    rules.push(Quotes::create_default());
-
-    rules
+    vec![
+        Quotes::create_default(),
+        NoUnresolvedImport::create_default(),
+    ]
 }
 
-pub fn create_rules() -> HashMap<String, fn(RuleEntry) -> Box<dyn RuleType>> {
+pub fn create_rules() -> HashMap<String, RuleBuilder> {
     let mut rules :  HashMap<String, RuleBuilder> = HashMap::new();
 
     rules.insert("quotes".to_string(), Quotes::create);
+    rules.insert(NoUnresolvedImport::RULE_ID.to_string(), NoUnresolvedImport::create);
 
     rules
 }
\ No newline at end of file
@@ -0,0 +1,67 @@
+use crate::linter::SolidFile;
+use crate::imports::ImportGraph;
+use crate::rules::types::*;
+use crate::rules::reporter::RuleReporter;
+use crate::types::*;
+
+pub struct NoUnresolvedImport {
+    data: RuleEntry
+}
+
+impl RuleType for NoUnresolvedImport {
+
+    fn diagnose_imports(&self, file: &SolidFile, _files: &Vec<SolidFile>, imports: &ImportGraph) -> Vec<LintDiag> {
+        let mut reporter = RuleReporter::new(&self.data, file);
+
+        for import in imports.imports(&file.path).iter().filter(|import| import.resolved.is_none()) {
+            reporter.report_src(&import.src, format!("Cannot find the imported file \"{}\"", import.path));
+        }
+        reporter.into_diags()
+    }
+}
+
+impl NoUnresolvedImport {
+    pub const RULE_ID : &'static str = "no-unresolved-import";
+
+    pub(crate) fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        Ok(Box::new(NoUnresolvedImport { data }))
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: NoUnresolvedImport::RULE_ID.to_string(),
+            severity: Severity::WARNING,
+            data: vec![],
+            options: None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solc_wrapper::ImportResolver;
+    use super::*;
+
+    #[test]
+    fn test_unresolved_imports() {
+        let file = SolidFile {
+            data: serde_json::from_str(&std::fs::read_to_string("tests/files/unresolved_imports/Importer.json").unwrap()).unwrap(),
+            path: "tests/files/unresolved_imports/Importer.sol".to_string(),
+            content: std::fs::read_to_string("tests/files/unresolved_imports/Importer.sol").unwrap(),
+        };
+        let resolver = ImportResolver {
+            roots: vec!["tests/files/unresolved_imports".into()],
+            remappings: vec!["@missing/=lib/missing/".to_string()],
+        };
+        let graph = ImportGraph::new(std::slice::from_ref(&file), &resolver);
+        let rule = NoUnresolvedImport { data: NoUnresolvedImport::create_default() };
+
+        let diags = rule.diagnose_imports(&file, &vec![], &graph);
+
+        let diags: Vec<(u64, &str)> = diags.iter().map(|diag| (diag.range.start.line, diag.message.as_str())).collect();
+        assert_eq!(diags, vec![
+            (5, "Cannot find the imported file \"./Missing.sol\""),
+            (6, "Cannot find the imported file \"@missing/Token.sol\""),
+        ]);
+    }
+}
@@ -1,7 +1,7 @@
-use std::ops::Index;
 use crate::linter::SolidFile;
-use solc_wrapper::*;
+use crate::range_from_offset;
 use crate::rules::types::*;
+use crate::rules::reporter::RuleReporter;
 use crate::types::*;
 
 
@@ -9,50 +9,111 @@ pub struct Quotes {
     data: RuleEntry
 }
 
+fn quote_edit(content: &str, offset: usize) -> TextEdit {
+    TextEdit {
+        range: range_from_offset(content, offset, 1),
+        new_text: "\"".to_string(),
+    }
+}
+
+// The offset of the quote closing the string opened at `start`, if it is closed on the same line
+fn string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut idx = start + 1;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'\\' => idx += 1,
+            b'\n' => return None,
+            c if c == bytes[start] => return Some(idx),
+            _ => {}
+        }
+        idx += 1;
+    }
+    None
+}
+
+// The offset following the comment starting at `start`
+fn comment_end(content: &str, start: usize) -> usize {
+    let (end, terminator) = match &content[start..start + 2] {
+        "//" => ("\n", 0),
+        _ => ("*/", 2),
+    };
+    content[start + 2..].find(end).map_or(content.len(), |idx| start + 2 + idx + terminator)
+}
+
 impl RuleType for Quotes {
 
-    fn diagnose(&self, file: &SolidFile, files: &Vec<SolidFile>) -> Vec<LintDiag> {
-        let mut res = Vec::new();
-        let mut line_idx = 1;
-
-        for line in file.content.lines() {
-            line.chars().enumerate().for_each(|(idx, c)| {
-                if c == '\'' && line.chars().nth(idx - 1).unwrap_or(' ') != '\\' {
-                    res.push(LintDiag {
-                        range: Range {
-                            start: Position { line: line_idx, character: idx as u64},
-                            end: Position { line: line_idx, character: idx as u64 },
-                            length: 1 as u64,
-                        },
-                        message: format!("Use double quotes instead of single quote"),
-                        severity: Some(self.data.severity),
-                        code: None,
-                        source: None,
-                        uri: file.path.clone(),
-                        source_file_content: file.content.clone()
-                    });
+    // Scans the content rather than the string literals of the AST, so that files solc can't parse are diagnosed too.
+    // Comments and double-quoted strings are skipped, as their apostrophes are not quotes.
+    fn diagnose(&self, file: &SolidFile, _files: &Vec<SolidFile>) -> Vec<LintDiag> {
+        let mut reporter = RuleReporter::new(&self.data, file);
+        let content = file.content.as_str();
+        let bytes = content.as_bytes();
+        let mut idx = 0;
+
+        while idx < bytes.len() {
+            match (bytes[idx], bytes.get(idx + 1)) {
+                (b'/', Some(b'/' | b'*')) => {
+                    idx = comment_end(content, idx);
+                    continue;
+                }
+                (b'"', _) => idx = string_end(bytes, idx).unwrap_or(idx),
+                (b'\'', _) => {
+                    let diag = reporter.report_offset(idx, 1, "Use double quotes instead of single quote");
+                    if let Some(end) = string_end(bytes, idx) {
+                        // The string can't be converted as is if it contains double quotes
+                        if !content[idx + 1..end].contains('"') {
+                            diag.fix = Some(vec![quote_edit(content, idx), quote_edit(content, end)]);
+                        }
+                        reporter.report_offset(end, 1, "Use double quotes instead of single quote");
+                        idx = end;
+                    }
                 }
-            });
-            line_idx += 1;
+                _ => {}
+            }
+            idx += 1;
         }
-        res
+        reporter.into_diags()
     }
 
-} 
+}
 
 impl Quotes {
-    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
-        let mut rule  = Quotes {
+    pub(crate) fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        let rule  = Quotes {
             data
         };
-        Box::new(rule)
+        Ok(Box::new(rule))
     }
 
     pub(crate) fn create_default() -> RuleEntry {
         RuleEntry {
             id: "quotes".to_string(),
             severity: Severity::ERROR,
-            data: vec![]
+            data: vec![],
+            options: None
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fix::apply_fixes;
+    use crate::linter::empty_source_unit;
+    use super::*;
+
+    #[test]
+    fn test_quotes_skip_comments_and_strings() {
+        let path = "tests/files/quotes/Quotes.sol";
+        let content = std::fs::read_to_string(path).unwrap();
+        let file = SolidFile { data: empty_source_unit(path, &content), path: path.to_string(), content };
+        let rule = Quotes { data: Quotes::create_default() };
+        let diags = rule.diagnose(&file, &vec![]);
+
+        // The apostrophes of the comments and of the double-quoted string aren't reported
+        let positions: Vec<(u64, u64)> = diags.iter().map(|diag| (diag.range.start.line, diag.range.start.character)).collect();
+        assert_eq!(positions, vec![(6, 29), (6, 35), (8, 26), (8, 35)]);
+        // A string containing double quotes can't be converted as is
+        let fixed = apply_fixes(&file.content, &diags).content;
+        assert_eq!(fixed, file.content.replace("'hello'", "\"hello\""));
+    }
+}
@@ -1,15 +1,19 @@
 use std::collections::HashMap;
-use crate::rules::types::{RuleEntry, RuleType};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use crate::rules::types::{RuleEntry, RuleType, RulesError};
 
 pub mod types;
 pub mod rule_impl;
 pub mod factory;
+pub mod reporter;
 
 // List all rules
 pub mod best_practises;
 pub mod miscellaneous;
 pub mod naming;
 pub mod order;
+pub mod project;
 
 pub fn create_default_rules() -> Vec<RuleEntry> {
     let mut rules = Vec::new();
@@ -18,11 +22,12 @@ pub fn create_default_rules() -> Vec<RuleEntry> {
     rules.append(&mut miscellaneous::create_default_rules());
     rules.append(&mut naming::create_default_rules());
     rules.append(&mut order::create_default_rules());
+    rules.append(&mut project::create_default_rules());
 
     rules
 }
 
-type RuleBuilder = fn(RuleEntry) -> Box<dyn RuleType>;
+type RuleBuilder = fn(RuleEntry) -> Result<Box<dyn RuleType>, RulesError>;
 
 pub fn add_rules(rules : &mut HashMap<String, RuleBuilder>, to_add: HashMap<String, RuleBuilder>) {
     for (key, value) in to_add {
@@ -30,13 +35,25 @@ pub fn add_rules(rules : &mut HashMap<String, RuleBuilder>, to_add: HashMap<Stri
     }
 }
 
-pub fn create_rules() -> HashMap<String, fn(RuleEntry) -> Box<dyn RuleType>> {
+type OptionsSchemaBuilder = fn(&mut SchemaGenerator) -> Schema;
+
+pub fn create_rules() -> HashMap<String, RuleBuilder> {
     let mut rules = HashMap::new();
 
     add_rules(&mut rules, best_practises::create_rules());
     add_rules(&mut rules, naming::create_rules());
     add_rules(&mut rules, order::create_rules());
     add_rules(&mut rules, miscellaneous::create_rules());
+    add_rules(&mut rules, project::create_rules());
 
     rules
-}
\ No newline at end of file
+}
+
+/// Lists the schema of the options of every rule taking options.
+pub fn create_options_schemas() -> HashMap<String, OptionsSchemaBuilder> {
+    let mut schemas = HashMap::new();
+
+    schemas.extend(best_practises::create_options_schemas());
+
+    schemas
+}
@@ -1,18 +1,31 @@
-use clap::builder::Str;
 use crate::linter::SolidFile;
 use crate::rules::types::*;
+use crate::rules::reporter::RuleReporter;
 use crate::types::*;
-use solc_wrapper::{ContractDefinitionChildNodes, decode_location, SourceUnit, SourceUnitChildNodes};
+use crate::rules::naming::{rename_edits, to_pascal_case};
+use solc_wrapper::{ContractDefinition, SourceUnitChildNodes};
 
 pub struct ContractNamePascalCase {
     data: RuleEntry
 }
 
+impl ContractNamePascalCase {
+    // Renames the contract along with its references in the file. It is only a suggestion: the other files
+    // importing the contract, and the deployment scripts using its artifact, would have to follow.
+    fn suggestion(file: &SolidFile, contract: &ContractDefinition) -> Option<Vec<TextEdit>> {
+        let name = to_pascal_case(&contract.name);
+        if name == contract.name {
+            return None;
+        }
+        rename_edits(file, contract.id, contract.name_location.as_ref()?, &contract.name, &name)
+    }
+}
+
 impl RuleType for ContractNamePascalCase {
 
-    fn diagnose(&self, file: &SolidFile, files: &Vec<SolidFile>) -> Vec<LintDiag> {
+    fn diagnose(&self, file: &SolidFile, _files: &Vec<SolidFile>) -> Vec<LintDiag> {
 
-        let mut res = Vec::new();
+        let mut reporter = RuleReporter::new(&self.data, file);
 
         for node in &file.data.nodes {
             match node {
@@ -21,42 +34,31 @@ impl RuleType for ContractNamePascalCase {
                         contract.name.contains("_") ||
                         contract.name.contains("-") {
                         //Untested
-                        let location = decode_location(contract.name_location.as_ref().unwrap(), &file.content);
-                        res.push(LintDiag {
-                            range: Range {
-                                start: Position { line: location.0.line as u64, character: location.0.column as u64 },
-                                end: Position { line: location.1.line as u64, character: location.1.column as u64 },
-                                length: location.0.length as u64,
-                            },
-                            message: format!("Contract name need to be in pascal case"),
-                            severity: Some(self.data.severity),
-                            code: None,
-                            source: None,
-                            uri: file.path.clone(),
-                            source_file_content: file.content.clone(),
-                        });
+                        let src = contract.name_location.as_ref().unwrap_or(&contract.src);
+                        reporter.report_src(src, "Contract name need to be in pascal case").suggestion = Self::suggestion(file, contract);
                     }
                 }
                 _ => { continue; }
             }
         }
-        res
+        reporter.into_diags()
     }
 }
 
 impl ContractNamePascalCase {
-    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
-        let mut rule  = ContractNamePascalCase {
+    pub(crate) fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        let rule  = ContractNamePascalCase {
             data
         };
-        Box::new(rule)
+        Ok(Box::new(rule))
     }
 
     pub(crate) fn create_default() -> RuleEntry {
         RuleEntry {
             id: "contract-name-pascalcase".to_string(),
             severity: Severity::WARNING,
-            data: vec![]
+            data: vec![],
+            options: None
         }
     }
 }
\ No newline at end of file
@@ -1,70 +1,82 @@
-use clap::builder::Str;
-use crate::linter::SolidFile;
-use crate::rules::types::*;
-use crate::types::*;
-use solc_wrapper::{ContractDefinitionChildNodes, decode_location, FunctionDefinitionKind, SourceUnit, SourceUnitChildNodes};
-
-pub struct FuncNameCamelCase {
-    data: RuleEntry
-}
-
-impl RuleType for FuncNameCamelCase {
-
-    fn diagnose(&self, file: &SolidFile, files: &Vec<SolidFile>) -> Vec<LintDiag> {
-
-        let mut res = Vec::new();
-
-        for node in &file.data.nodes {
-            match node {
-                SourceUnitChildNodes::ContractDefinition(contract) => {
-                    for node in &contract.nodes {
-                        match node {
-                            ContractDefinitionChildNodes::FunctionDefinition(function) => {
-                                if function.kind != FunctionDefinitionKind::Constructor
-                                    && (!(function.name.chars().nth(0).unwrap_or(' ') >= 'a' && function.name.chars().nth(0).unwrap_or(' ') <= 'z')
-                                        || function.name.contains('_')
-                                        || function.name.contains('-')) {
-                                    //Untested
-                                    let location = decode_location(function.name_location.as_ref().unwrap(), &file.content);
-                                    res.push(LintDiag {
-                                        range: Range {
-                                            start: Position { line: location.0.line as u64, character: location.0.column as u64 },
-                                            end: Position { line: location.1.line as u64, character: location.1.column as u64 },
-                                            length: location.0.length as u64
-                                        },
-                                        message: format!("Function name need to be in camel case"),
-                                        severity: Some(self.data.severity),
-                                        code: None,
-                                        source: None,
-                                        uri: file.path.clone(),
-                                        source_file_content: file.content.clone(),
-                                    });
-                                }
-                            }
-                            _ => { continue; }
-                        }
-                    }
-                }
-                _ => { continue; }
-            }
-        }
-        res
-    }
-}
-
-impl FuncNameCamelCase {
-    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
-        let mut rule  = FuncNameCamelCase {
-            data
-        };
-        Box::new(rule)
-    }
-
-    pub(crate) fn create_default() -> RuleEntry {
-        RuleEntry {
-            id: "func-name-camelcase".to_string(),
-            severity: Severity::WARNING,
-            data: vec![]
-        }
-    }
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::rules::reporter::RuleReporter;
+use crate::types::*;
+use crate::rules::naming::{private_rename_edits, rename_edits, to_camel_case};
+use solc_wrapper::{ContractDefinition, ContractDefinitionChildNodes, FunctionDefinition, FunctionDefinitionKind, SourceUnitChildNodes, Visibility};
+
+pub struct FuncNameCamelCase {
+    data: RuleEntry
+}
+
+impl FuncNameCamelCase {
+    // Renames the function along with its references in the file. Only private functions are renamed by `--fix`:
+    // the others may be called from other files, by derived contracts or through the ABI.
+    fn fix(file: &SolidFile, contract: &ContractDefinition, function: &FunctionDefinition, diag: &mut LintDiag) {
+        let name = to_camel_case(&function.name);
+        let name_location = match &function.name_location {
+            Some(name_location) if name != function.name => name_location,
+            _ => return,
+        };
+        let edits = rename_edits(file, function.id, name_location, &function.name, &name);
+        let is_local = function.visibility == Some(Visibility::Private)
+            && !function.is_virtual
+            && function.overrides.is_none();
+        match is_local {
+            // The parse-only ASTs don't resolve references, a private function can be renamed without them
+            true => diag.fix = edits.or_else(|| private_rename_edits(file, contract, function, &name)),
+            false => diag.suggestion = edits,
+        }
+    }
+}
+
+impl RuleType for FuncNameCamelCase {
+
+    fn diagnose(&self, file: &SolidFile, _files: &Vec<SolidFile>) -> Vec<LintDiag> {
+
+        let mut reporter = RuleReporter::new(&self.data, file);
+
+        for node in &file.data.nodes {
+            match node {
+                SourceUnitChildNodes::ContractDefinition(contract) => {
+                    for node in &contract.nodes {
+                        match node {
+                            ContractDefinitionChildNodes::FunctionDefinition(function) => {
+                                if function.kind != FunctionDefinitionKind::Constructor
+                                    && (!(function.name.chars().nth(0).unwrap_or(' ') >= 'a' && function.name.chars().nth(0).unwrap_or(' ') <= 'z')
+                                        || function.name.contains('_')
+                                        || function.name.contains('-')) {
+                                    //Untested
+                                    let src = function.name_location.as_ref().unwrap_or(&function.src);
+                                    let diag = reporter.report_src(src, "Function name need to be in camel case");
+                                    Self::fix(file, contract, function, diag);
+                                }
+                            }
+                            _ => { continue; }
+                        }
+                    }
+                }
+                _ => { continue; }
+            }
+        }
+        reporter.into_diags()
+    }
+}
+
+impl FuncNameCamelCase {
+    pub(crate) fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        let rule  = FuncNameCamelCase {
+            data
+        };
+        Ok(Box::new(rule))
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: "func-name-camelcase".to_string(),
+            severity: Severity::WARNING,
+            data: vec![],
+            options: None
+        }
+    }
 }
\ No newline at end of file
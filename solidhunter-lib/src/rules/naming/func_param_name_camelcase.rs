@@ -1,8 +1,8 @@
-use clap::builder::Str;
 use crate::linter::SolidFile;
 use crate::rules::types::*;
+use crate::rules::reporter::RuleReporter;
 use crate::types::*;
-use solc_wrapper::{ContractDefinitionChildNodes, decode_location, SourceUnit, SourceUnitChildNodes};
+use solc_wrapper::{ContractDefinitionChildNodes, SourceUnitChildNodes};
 
 pub struct FuncParamNameCamelcase {
     data: RuleEntry
@@ -10,9 +10,9 @@ pub struct FuncParamNameCamelcase {
 
 impl RuleType for FuncParamNameCamelcase {
 
-    fn diagnose(&self, file: &SolidFile, files: &Vec<SolidFile>) -> Vec<LintDiag> {
+    fn diagnose(&self, file: &SolidFile, _files: &Vec<SolidFile>) -> Vec<LintDiag> {
 
-        let mut res = Vec::new();
+        let mut reporter = RuleReporter::new(&self.data, file);
 
         for node in &file.data.nodes {
             match node {
@@ -25,20 +25,8 @@ impl RuleType for FuncParamNameCamelcase {
                                         parameter.name.contains("_") ||
                                         parameter.name.contains("-") {
                                         //Untested
-                                        let location = decode_location(parameter.name_location.as_ref().unwrap(), &file.content);
-                                        res.push(LintDiag {
-                                            range: Range {
-                                                start: Position { line: location.0.line as u64, character: location.0.column as u64 },
-                                                end: Position { line: location.1.line as u64, character: location.1.column as u64 },
-                                                length: location.0.length as u64,
-                                            },
-                                            message: format!("Parameter name need to be in camel case"),
-                                            severity: Some(self.data.severity),
-                                            code: None,
-                                            source: None,
-                                            uri: file.path.clone(),
-                                            source_file_content: file.content.clone(),
-                                        });
+                                        let src = parameter.name_location.as_ref().unwrap_or(&parameter.src);
+                                        reporter.report_src(src, "Parameter name need to be in camel case");
                                     }
                                 }
                             }
@@ -50,23 +38,24 @@ impl RuleType for FuncParamNameCamelcase {
                 _ => { continue; }
             }
         }
-        res
+        reporter.into_diags()
     }
 }
 
 impl FuncParamNameCamelcase {
-    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
-        let mut rule  = FuncParamNameCamelcase {
+    pub(crate) fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        let rule  = FuncParamNameCamelcase {
             data
         };
-        Box::new(rule)
+        Ok(Box::new(rule))
     }
 
     pub(crate) fn create_default() -> RuleEntry {
         RuleEntry {
             id: "func-param-name-camelcase".to_string(),
             severity: Severity::WARNING,
-            data: vec![]
+            data: vec![],
+            options: None
         }
     }
 }
\ No newline at end of file
@@ -1,8 +1,16 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use solc_wrapper::{ContractDefinition, EnumDefinition, ErrorDefinition, EventDefinition, FunctionDefinition, Identifier,
+    IdentifierPath, MemberAccess, ModifierDefinition, StructDefinition, UserDefinedTypeName,
+    UserDefinedValueTypeDefinition, VariableDeclaration};
+use solc_wrapper::ast::visitor::Visitor;
+use solc_wrapper::ast::visitor::visit::{walk_contract_definition, walk_source_unit};
+use crate::fix::replace_src;
+use crate::linter::SolidFile;
+use crate::types::TextEdit;
 use crate::rules::naming::contract_name_pascalcase::ContractNamePascalCase;
 use crate::rules::naming::func_name_camelcase::FuncNameCamelCase;
 use crate::rules::naming::use_forbidden_name::UseForbiddenName;
-use crate::rules::types::{RuleEntry, RuleType};
+use crate::rules::types::RuleEntry;
 use crate::rules::naming::func_param_name_camelcase::FuncParamNameCamelcase;
 use crate::rules::RuleBuilder;
 
@@ -12,21 +20,190 @@ pub(crate) mod contract_name_pascalcase;
 pub(crate) mod func_name_camelcase;
 pub(crate) mod use_forbidden_name;
 
-// List all rules
+// Converts `foo_bar`, `foo-bar` or `FooBar` to `fooBar`, keeping leading underscores
+pub(crate) fn to_camel_case(name: &str) -> String {
+    let pascal = to_pascal_case(name);
+    let prefix_len = pascal.len() - pascal.trim_start_matches('_').len();
+    let mut chars = pascal[prefix_len..].chars();
 
+    match chars.next() {
+        Some(first) => format!("{}{}{}", &pascal[..prefix_len], first.to_lowercase(), chars.as_str()),
+        None => pascal,
+    }
+}
 
-pub fn create_default_rules() -> Vec<RuleEntry> {
-    let mut rules = Vec::new();
+// Converts `foo_bar`, `foo-bar` or `fooBar` to `FooBar`, keeping leading underscores
+pub(crate) fn to_pascal_case(name: &str) -> String {
+    let trimmed = name.trim_start_matches('_');
+    let mut res = name[..name.len() - trimmed.len()].to_string();
 
-    rules.push(FuncParamNameCamelcase::create_default());
-    rules.push(ContractNamePascalCase::create_default());
-    rules.push(FuncNameCamelCase::create_default());
-    rules.push(UseForbiddenName::create_default());
+    for word in trimmed.split(['_', '-']).filter(|w| !w.is_empty()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            res.extend(first.to_uppercase());
+            res.push_str(chars.as_str());
+        }
+    }
+    res
+}
 
-    rules
+// A node that may refer to a declaration by its name
+struct Reference {
+    name: String,
+    // The src of the name
+    src: String,
+    declaration: Option<usize>,
+    // Whether it is the member of an expression, e.g. `this.foo`
+    member: bool,
+}
+
+// The references and the declarations of a subtree, declarations as (name, id)
+#[derive(Default)]
+struct ReferenceCollector {
+    references: Vec<Reference>,
+    declarations: Vec<(String, usize)>,
+}
+
+impl ReferenceCollector {
+    fn reference(&mut self, name: &str, src: &str, declaration: Option<usize>, member: bool) {
+        self.references.push(Reference { name: name.to_string(), src: src.to_string(), declaration, member });
+    }
+
+    fn declaration(&mut self, name: &str, id: usize) {
+        self.declarations.push((name.to_string(), id));
+    }
+
+    // Whether `name` is used or declared, so that renaming something to it could change the meaning of the code
+    fn is_taken(&self, name: &str) -> bool {
+        self.references.iter().any(|reference| reference.name == name)
+            || self.declarations.iter().any(|(declaration, _)| declaration == name)
+    }
+}
+
+impl Visitor for ReferenceCollector {
+    fn visit_identifier(&mut self, node: &Identifier) {
+        self.reference(&node.name, &node.src, node.referenced_declaration, false);
+    }
+
+    fn visit_member_access(&mut self, node: &MemberAccess) {
+        self.reference(&node.member_name, &node.member_location, node.referenced_declaration, true);
+    }
+
+    fn visit_identifier_path(&mut self, node: &IdentifierPath) {
+        self.reference(&node.name, &node.src, node.referenced_declaration, false);
+    }
+
+    fn visit_user_defined_type_name(&mut self, node: &UserDefinedTypeName) {
+        // The path node, if any, is visited as well
+        if node.path_node.is_none() {
+            if let Some(name) = &node.name {
+                self.reference(name, &node.src, node.referenced_declaration, false);
+            }
+        }
+    }
+
+    fn visit_contract_definition(&mut self, node: &ContractDefinition) {
+        self.declaration(&node.name, node.id);
+    }
+
+    fn visit_function_definition(&mut self, node: &FunctionDefinition) {
+        self.declaration(&node.name, node.id);
+    }
+
+    fn visit_modifier_definition(&mut self, node: &ModifierDefinition) {
+        self.declaration(&node.name, node.id);
+    }
+
+    fn visit_struct_definition(&mut self, node: &StructDefinition) {
+        self.declaration(&node.name, node.id);
+    }
+
+    fn visit_enum_definition(&mut self, node: &EnumDefinition) {
+        self.declaration(&node.name, node.id);
+    }
+
+    fn visit_error_definition(&mut self, node: &ErrorDefinition) {
+        self.declaration(&node.name, node.id);
+    }
+
+    fn visit_event_definition(&mut self, node: &EventDefinition) {
+        self.declaration(&node.name, node.id);
+    }
+
+    fn visit_user_defined_value_type_definition(&mut self, node: &UserDefinedValueTypeDefinition) {
+        self.declaration(&node.name, node.id);
+    }
+
+    fn visit_variable_declaration(&mut self, node: &VariableDeclaration) {
+        self.declaration(&node.name, node.id);
+    }
+}
+
+// The edits renaming the declaration `id` from `name` to `new_name`, along with its references in the file.
+// References are told apart by the declaration id solc resolves them to, so that a local or a member of another
+// contract named the same is left as is. On a parse-only AST, where references aren't resolved, there is no edit.
+pub(crate) fn rename_edits(file: &SolidFile, id: usize, name_location: &str, name: &str, new_name: &str) -> Option<Vec<TextEdit>> {
+    let mut collector = ReferenceCollector::default();
+    walk_source_unit(&mut collector, &file.data);
+    // Renaming could change the meaning of the code
+    if collector.references.iter().any(|reference| reference.name == new_name) {
+        return None;
+    }
+
+    let mut edits = vec![replace_src(&file.content, name_location, name, new_name)?];
+    let mut renamed: HashSet<String> = HashSet::new();
+    for reference in collector.references.iter().filter(|reference| reference.name == name) {
+        match reference.declaration {
+            Some(declaration) if declaration == id => {
+                if renamed.insert(reference.src.clone()) {
+                    edits.push(replace_src(&file.content, &reference.src, name, new_name)?);
+                }
+            }
+            Some(_) => continue,
+            // Builtins have no declaration either, but can't be named like a user declaration
+            None => return None,
+        }
+    }
+    Some(edits)
+}
+
+// The edits renaming the private function `function` of `contract` to `new_name`, without relying on resolved
+// references. Only the contract can call a private function, and not as a member, so the identifiers of the contract
+// named like it are its references, as long as nothing else of the contract is named the same, e.g. a local variable.
+pub(crate) fn private_rename_edits(file: &SolidFile, contract: &ContractDefinition, function: &FunctionDefinition, new_name: &str) -> Option<Vec<TextEdit>> {
+    let mut file_names = ReferenceCollector::default();
+    walk_source_unit(&mut file_names, &file.data);
+    if file_names.is_taken(new_name) {
+        return None;
+    }
+    let mut collector = ReferenceCollector::default();
+    walk_contract_definition(&mut collector, contract);
+    if collector.declarations.iter().any(|(name, id)| *name == function.name && *id != function.id) {
+        return None;
+    }
+
+    let mut edits = vec![replace_src(&file.content, function.name_location.as_ref()?, &function.name, new_name)?];
+    let references = collector.references.iter()
+        .filter(|reference| reference.name == function.name && !reference.member);
+    for reference in references {
+        edits.push(replace_src(&file.content, &reference.src, &function.name, new_name)?);
+    }
+    Some(edits)
+}
+
+// List all rules
+
+
+pub fn create_default_rules() -> Vec<RuleEntry> {
+    vec![
+        FuncParamNameCamelcase::create_default(),
+        ContractNamePascalCase::create_default(),
+        FuncNameCamelCase::create_default(),
+        UseForbiddenName::create_default(),
+    ]
 }
 
-pub fn create_rules() -> HashMap<String, fn(RuleEntry) -> Box<dyn RuleType>> {
+pub fn create_rules() -> HashMap<String, RuleBuilder> {
     let mut rules :  HashMap<String, RuleBuilder> = HashMap::new();
 
     rules.insert( "func-param-name-camelcase".to_string(), FuncParamNameCamelcase::create);
@@ -36,3 +213,84 @@ pub fn create_rules() -> HashMap<String, fn(RuleEntry) -> Box<dyn RuleType>> {
 
     rules
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::apply_fixes;
+    use crate::rules::types::RuleType;
+    use crate::types::Severity;
+
+    fn rename_file(resolved: bool) -> SolidFile {
+        let content = std::fs::read_to_string("tests/files/naming/Rename.sol").unwrap();
+        let mut ast = std::fs::read_to_string("tests/files/naming/Rename.json").unwrap();
+        if !resolved {
+            // As output by the parser
+            ast = ast.lines().filter(|line| !line.contains("referencedDeclaration")).collect();
+        }
+        SolidFile {
+            data: serde_json::from_str(&ast).unwrap(),
+            path: "Rename.sol".to_string(),
+            content,
+        }
+    }
+
+    fn rule(create: RuleBuilder, id: &str) -> Box<dyn RuleType> {
+        create(RuleEntry { id: id.to_string(), severity: Severity::WARNING, data: vec![], options: None }).unwrap()
+    }
+
+    #[test]
+    fn test_rename_fixes_only_private_functions() {
+        let file = rename_file(true);
+        let diags = rule(FuncNameCamelCase::create, "func-name-camelcase").diagnose(&file, &vec![]);
+
+        assert_eq!(diags.len(), 3);
+        // The public function keeps its name, and the local named like it is not one of its references
+        assert!(diags[0].fix.is_none());
+        assert_eq!(diags[0].suggestion.as_ref().map(Vec::len), Some(2));
+        // Derived contracts may call the internal one
+        assert!(diags[1].fix.is_none());
+        assert_eq!(diags[1].suggestion.as_ref().map(Vec::len), Some(2));
+        let fixed = apply_fixes(&file.content, &diags).content;
+        assert!(fixed.contains("function Deposit() public {}"));
+        assert!(fixed.contains("uint Deposit = 1;\n        Deposit;"));
+        assert!(fixed.contains("this.Deposit();"));
+        assert!(fixed.contains("function _Withdraw() internal {}"));
+        assert!(fixed.contains("        _Withdraw();"));
+        assert!(fixed.contains("function _transfer() private {}"));
+        assert!(fixed.contains("        _transfer();"));
+
+        let diags = rule(ContractNamePascalCase::create, "contract-name-pascalcase").diagnose(&file, &vec![]);
+        assert!(diags[0].fix.is_none());
+        assert_eq!(diags[0].suggestion.as_ref().map(|edits| edits[0].new_text.as_str()), Some("Vault"));
+    }
+
+    #[test]
+    fn test_rename_without_resolved_references() {
+        let file = rename_file(false);
+        let diags = rule(FuncNameCamelCase::create, "func-name-camelcase").diagnose(&file, &vec![]);
+
+        // The references of the functions that aren't private can't be told apart
+        assert!(diags[..2].iter().all(|diag| diag.fix.is_none() && diag.suggestion.is_none()));
+        // A private function is only called by its contract
+        let fixed = apply_fixes(&file.content, &diags).content;
+        assert!(fixed.contains("function _transfer() private {}"));
+        assert!(fixed.contains("        _transfer();"));
+    }
+
+    #[test]
+    fn test_to_camel_case() {
+        assert_eq!(to_camel_case("foo_bar"), "fooBar");
+        assert_eq!(to_camel_case("Foo-bar"), "fooBar");
+        assert_eq!(to_camel_case("_FooBar"), "_fooBar");
+        assert_eq!(to_camel_case("fooBar"), "fooBar");
+    }
+
+    #[test]
+    fn test_to_pascal_case() {
+        assert_eq!(to_pascal_case("foo_bar"), "FooBar");
+        assert_eq!(to_pascal_case("foo-bar"), "FooBar");
+        assert_eq!(to_pascal_case("fooBar"), "FooBar");
+        assert_eq!(to_pascal_case("__foo"), "__Foo");
+    }
+}
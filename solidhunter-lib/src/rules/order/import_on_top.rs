@@ -1,18 +1,64 @@
-use clap::builder::Str;
 use crate::linter::SolidFile;
 use crate::rules::types::*;
+use crate::rules::reporter::RuleReporter;
 use crate::types::*;
-use solc_wrapper::{ContractDefinitionChildNodes, decode_location, SourceUnit, SourceUnitChildNodes};
+use crate::fix::decode_src;
+use crate::range_from_offset;
+use solc_wrapper::{ImportDirective, SourceUnitChildNodes};
 
 pub struct ImportOnTop {
     data: RuleEntry
 }
 
+fn node_src(node: &SourceUnitChildNodes) -> Option<&str> {
+    match node {
+        SourceUnitChildNodes::ContractDefinition(node) => Some(&node.src),
+        SourceUnitChildNodes::StructDefinition(node) => Some(&node.src),
+        SourceUnitChildNodes::EnumDefinition(node) => Some(&node.src),
+        SourceUnitChildNodes::ErrorDefinition(node) => Some(&node.src),
+        SourceUnitChildNodes::PragmaDirective(node) => Some(&node.src),
+        SourceUnitChildNodes::ImportDirective(node) => Some(&node.src),
+        SourceUnitChildNodes::UsingForDirective(node) => Some(&node.src),
+        SourceUnitChildNodes::Other(_) => None,
+    }
+}
+
+impl ImportOnTop {
+    // Moves the import right after `anchor`, the last node of the header
+    fn fix(content: &str, import: &ImportDirective, anchor: &SourceUnitChildNodes) -> Option<Vec<TextEdit>> {
+        let (start, length) = decode_src(&import.src)?;
+        let (anchor_start, anchor_length) = decode_src(node_src(anchor)?)?;
+        let text = content.get(start..start + length)?;
+        let insert_at = anchor_start + anchor_length;
+
+        // Remove the whole line when the import is alone on it
+        let line_start = content[..start].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = content[start + length..].find('\n').map_or(content.len(), |idx| start + length + idx + 1);
+        let line = &content[line_start..line_end];
+        let (delete_start, delete_end) = if line.trim() == text {
+            (line_start, line_end)
+        } else {
+            (start, start + length)
+        };
+
+        Some(vec![
+            TextEdit {
+                range: range_from_offset(content, insert_at, 0),
+                new_text: format!("{}{}", if content.contains("\r\n") { "\r\n" } else { "\n" }, text),
+            },
+            TextEdit {
+                range: range_from_offset(content, delete_start, delete_end - delete_start),
+                new_text: String::new(),
+            },
+        ])
+    }
+}
+
 impl RuleType for ImportOnTop {
 
-    fn diagnose(&self, file: &SolidFile, files: &Vec<SolidFile>) -> Vec<LintDiag> {
+    fn diagnose(&self, file: &SolidFile, _files: &Vec<SolidFile>) -> Vec<LintDiag> {
 
-        let mut res = Vec::new();
+        let mut reporter = RuleReporter::new(&self.data, file);
         let mut last_import_location = 0;
 
         for i in 1..file.data.nodes.len() {
@@ -27,47 +73,32 @@ impl RuleType for ImportOnTop {
         }
 
         for i in 1..file.data.nodes.len() {
-            match &file.data.nodes[i] {
-                SourceUnitChildNodes::ImportDirective(import) => {
-                    if i > last_import_location {
-                        let location = decode_location(&import.src, &file.content);
-                        
-                        res.push(LintDiag {
-                            range: Range {
-                                start: Position { line: location.0.line as u64, character: location.0.column as u64 },
-                                end: Position { line: location.1.line as u64, character: location.1.column as u64 },
-                                length: location.0.length as u64
-                            },
-                            message: format!("Import must be on top in the file"),
-                            severity: Some(self.data.severity),
-                            code: None,
-                            source: None,
-                            uri: file.path.clone(),
-                            source_file_content: file.content.clone(),
-                        });
-                    }
-                }
-                _ => {}
+            if let SourceUnitChildNodes::ImportDirective(import) = &file.data.nodes[i] {
+                if i > last_import_location {
+                    reporter.report_src(&import.src, "Import must be on top in the file").fix =
+                        Self::fix(&file.content, import, &file.data.nodes[last_import_location]);
+                }
             }
         }
 
-        res
+        reporter.into_diags()
     }
 }
 
 impl ImportOnTop {
-    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
-        let mut rule  = ImportOnTop {
+    pub(crate) fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        let rule  = ImportOnTop {
             data
         };
-        Box::new(rule)
+        Ok(Box::new(rule))
     }
 
     pub(crate) fn create_default() -> RuleEntry {
         RuleEntry {
             id: "import-on-top".to_string(),
             severity: Severity::WARNING,
-            data: vec![]
+            data: vec![],
+            options: None
         }
     }
 }
\ No newline at end of file
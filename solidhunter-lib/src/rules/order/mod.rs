@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use crate::rules::types::{RuleEntry, RuleType};
+use crate::rules::types::RuleEntry;
 
 #[macro_use]
 pub(crate) mod import_on_top;
@@ -10,14 +10,12 @@ use crate::rules::order::import_on_top::ImportOnTop;
 use crate::rules::RuleBuilder;
 
 pub fn create_default_rules() -> Vec<RuleEntry> {
-    let mut rules = Vec::new();
-
-    rules.push(ImportOnTop::create_default());
-
-    rules
+    vec![
+        ImportOnTop::create_default(),
+    ]
 }
 
-pub fn create_rules() -> HashMap<String, fn(RuleEntry) -> Box<dyn RuleType>> {
+pub fn create_rules() -> HashMap<String, RuleBuilder> {
     let mut rules :  HashMap<String, RuleBuilder> = HashMap::new();
 
     rules.insert( "import-on-top".to_string(), ImportOnTop::create);
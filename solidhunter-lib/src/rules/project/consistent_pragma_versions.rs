@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+use crate::fix::decode_src;
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::rules::reporter::RuleReporter;
+use crate::types::*;
+use solc_wrapper::{PragmaDirective, SourceUnitChildNodes};
+
+/// Files whose `pragma solidity` differs from the one most of the project files have.
+pub struct ConsistentPragmaVersions {
+    data: RuleEntry
+}
+
+// The version requirement of the first `pragma solidity` of the file, with its whitespaces normalized
+fn version_pragma(file: &SolidFile) -> Option<(&PragmaDirective, String)> {
+    let pragma = file.data.nodes.iter().find_map(|node| match node {
+        SourceUnitChildNodes::PragmaDirective(pragma) if pragma.literals.first().is_some_and(|literal| literal == "solidity") => Some(pragma),
+        _ => None,
+    })?;
+    // The literals lose the spaces between the ranges of the requirement, the source keeps them
+    let text = decode_src(&pragma.src)
+        .and_then(|(offset, length)| file.content.get(offset..offset + length))
+        .and_then(|text| text.trim().strip_prefix("pragma")?.trim_start().strip_prefix("solidity"))
+        .map(|text| text.trim_end().trim_end_matches(';').split_whitespace().collect::<Vec<&str>>().join(" "))
+        .unwrap_or_else(|| pragma.literals[1..].concat());
+    Some((pragma.as_ref(), text))
+}
+
+impl RuleType for ConsistentPragmaVersions {
+
+    fn project_rule(&self) -> Option<&dyn ProjectRule> {
+        Some(self)
+    }
+}
+
+impl ProjectRule for ConsistentPragmaVersions {
+
+    fn diagnose_project(&self, project: &ProjectFiles) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let pragmas: Vec<(&SolidFile, &PragmaDirective, String)> = project.project_files()
+            .filter_map(|file| version_pragma(file).map(|(pragma, version)| (file, pragma, version)))
+            .collect();
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for (_, _, version) in &pragmas {
+            *counts.entry(version.as_str()).or_default() += 1;
+        }
+        // On a tie, the first requirement in order is the expected one
+        let (expected, count) = match counts.iter().max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0))) {
+            Some((expected, count)) if counts.len() > 1 => (*expected, *count),
+            _ => return res,
+        };
+
+        for (file, pragma, version) in &pragmas {
+            if version == expected {
+                continue;
+            }
+            let mut reporter = RuleReporter::new(&self.data, file);
+            reporter.report_src(&pragma.src, format!(
+                "Solidity version \"{}\" differs from \"{}\", required by {} of the {} files",
+                version, expected, count, pragmas.len()
+            ));
+            res.append(&mut reporter.into_diags());
+        }
+        res
+    }
+}
+
+impl ConsistentPragmaVersions {
+    pub const RULE_ID : &'static str = "consistent-pragma-versions";
+
+    pub(crate) fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        Ok(Box::new(ConsistentPragmaVersions { data }))
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: ConsistentPragmaVersions::RULE_ID.to_string(),
+            severity: Severity::WARNING,
+            data: vec![],
+            options: None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::imports::ImportGraph;
+    use crate::rules::project::tests::{file, pragma};
+    use super::*;
+
+    #[test]
+    fn test_consistent_pragma_versions() {
+        let files = vec![
+            file("A.sol", "pragma solidity ^0.8.0;\n", vec![pragma("0:23:0")]),
+            file("B.sol", "pragma  solidity   ^0.8.0 ;\n", vec![pragma("0:27:0")]),
+            file("C.sol", "pragma solidity >=0.7.0  <0.9.0;\n", vec![pragma("0:32:0")]),
+            file("D.sol", "contract D {}\n", vec![]),
+        ];
+        let graph = ImportGraph::default();
+        let context_files = HashSet::new();
+        let rule = ConsistentPragmaVersions { data: ConsistentPragmaVersions::create_default() };
+
+        let diags = rule.diagnose_project(&ProjectFiles::new(&files, &graph, &context_files));
+
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].uri, "C.sol");
+        assert_eq!(diags[0].message, "Solidity version \">=0.7.0 <0.9.0\" differs from \"^0.8.0\", required by 2 of the 3 files");
+
+        let diags = rule.diagnose_project(&ProjectFiles::new(&files[..2], &graph, &context_files));
+        assert!(diags.is_empty());
+    }
+}
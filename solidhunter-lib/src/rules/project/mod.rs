@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use crate::rules::types::RuleEntry;
+
+pub mod no_duplicate_contract_names;
+pub mod no_circular_imports;
+pub mod consistent_pragma_versions;
+pub mod no_unused_files;
+
+// List all rules
+
+use crate::rules::project::no_duplicate_contract_names::NoDuplicateContractNames;
+use crate::rules::project::no_circular_imports::NoCircularImports;
+use crate::rules::project::consistent_pragma_versions::ConsistentPragmaVersions;
+use crate::rules::project::no_unused_files::NoUnusedFiles;
+use crate::rules::RuleBuilder;
+
+pub fn create_default_rules() -> Vec<RuleEntry> {
+    vec![ConsistentPragmaVersions::create_default()]
+}
+
+pub fn create_rules() -> HashMap<String, RuleBuilder> {
+    let mut rules :  HashMap<String, RuleBuilder> = HashMap::new();
+
+    rules.insert(NoDuplicateContractNames::RULE_ID.to_string(), NoDuplicateContractNames::create);
+    rules.insert(NoCircularImports::RULE_ID.to_string(), NoCircularImports::create);
+    rules.insert(ConsistentPragmaVersions::RULE_ID.to_string(), ConsistentPragmaVersions::create);
+    rules.insert(NoUnusedFiles::RULE_ID.to_string(), NoUnusedFiles::create);
+
+    rules
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{json, Value};
+    use crate::linter::SolidFile;
+
+    /// A file made of `nodes`, `contract` and `pragma` build them.
+    pub(crate) fn file(path: &str, content: &str, nodes: Vec<Value>) -> SolidFile {
+        let data = serde_json::from_value(json!({
+            "id": 0,
+            "src": format!("0:{}:0", content.len()),
+            "nodeType": "SourceUnit",
+            "absolutePath": path,
+            "exportedSymbols": {},
+            "nodes": nodes,
+        })).unwrap();
+
+        SolidFile {
+            data,
+            path: path.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    pub(crate) fn contract(name: &str, kind: &str, is_abstract: bool, src: &str) -> Value {
+        json!({
+            "id": 1,
+            "src": src,
+            "name": name,
+            "abstract": is_abstract,
+            "baseContracts": [],
+            "contractDependencies": [],
+            "contractKind": kind,
+            "nodes": [],
+            "usedErrors": [],
+            "nodeType": "ContractDefinition",
+        })
+    }
+
+    pub(crate) fn pragma(src: &str) -> Value {
+        json!({
+            "id": 2,
+            "src": src,
+            "literals": ["solidity"],
+            "nodeType": "PragmaDirective",
+        })
+    }
+}
@@ -0,0 +1,53 @@
+use crate::rules::types::*;
+use crate::rules::reporter::RuleReporter;
+use crate::types::*;
+
+/// Files importing, directly or through other files, a file that imports them back.
+pub struct NoCircularImports {
+    data: RuleEntry
+}
+
+impl RuleType for NoCircularImports {
+
+    fn project_rule(&self) -> Option<&dyn ProjectRule> {
+        Some(self)
+    }
+
+    fn needs_imported_files(&self) -> bool {
+        true
+    }
+}
+
+impl ProjectRule for NoCircularImports {
+
+    fn diagnose_project(&self, project: &ProjectFiles) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for file in project.project_files() {
+            let mut reporter = RuleReporter::new(&self.data, file);
+            for import in project.imports.imports(&file.path) {
+                let imported = match &import.resolved {
+                    Some(imported) => imported.as_str(),
+                    None => continue,
+                };
+                let chain = match imported == file.path {
+                    true => Some(vec![imported]),
+                    false => project.imports.import_chain(imported, &file.path),
+                };
+                if let Some(chain) = chain {
+                    reporter.report_src(&import.src, format!("Circular import: {} -> {}", file.path, chain.join(" -> ")));
+                }
+            }
+            res.append(&mut reporter.into_diags());
+        }
+        res
+    }
+}
+
+impl NoCircularImports {
+    pub const RULE_ID : &'static str = "no-circular-imports";
+
+    pub(crate) fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        Ok(Box::new(NoCircularImports { data }))
+    }
+}
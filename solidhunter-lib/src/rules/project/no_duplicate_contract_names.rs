@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::rules::reporter::RuleReporter;
+use crate::types::*;
+use solc_wrapper::{ContractDefinition, SourceUnitChildNodes};
+
+/// Contracts, interfaces and libraries sharing a name with one of another file, which makes the artifacts
+/// and the imports of the project ambiguous.
+pub struct NoDuplicateContractNames {
+    data: RuleEntry
+}
+
+fn contracts(file: &SolidFile) -> impl Iterator<Item = &ContractDefinition> {
+    file.data.nodes.iter().filter_map(|node| match node {
+        SourceUnitChildNodes::ContractDefinition(contract) => Some(contract.as_ref()),
+        _ => None,
+    })
+}
+
+impl RuleType for NoDuplicateContractNames {
+
+    fn project_rule(&self) -> Option<&dyn ProjectRule> {
+        Some(self)
+    }
+
+    fn needs_imported_files(&self) -> bool {
+        true
+    }
+}
+
+impl ProjectRule for NoDuplicateContractNames {
+
+    fn diagnose_project(&self, project: &ProjectFiles) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        // The dependencies count too, a project contract shouldn't shadow one of them
+        let mut definitions: HashMap<&str, Vec<&str>> = HashMap::new();
+        for file in project.files {
+            for contract in contracts(file) {
+                definitions.entry(contract.name.as_str()).or_default().push(file.path.as_str());
+            }
+        }
+
+        for file in project.project_files() {
+            let mut reporter = RuleReporter::new(&self.data, file);
+            for contract in contracts(file) {
+                let mut others: Vec<&str> = definitions[contract.name.as_str()].iter()
+                    .copied()
+                    .filter(|path| *path != file.path)
+                    .collect();
+                if others.is_empty() {
+                    continue;
+                }
+                others.sort();
+                others.dedup();
+                let src = contract.name_location.as_ref().unwrap_or(&contract.src);
+                reporter.report_src(src, format!("\"{}\" is also defined in {}", contract.name, others.join(", ")));
+            }
+            res.append(&mut reporter.into_diags());
+        }
+        res
+    }
+}
+
+impl NoDuplicateContractNames {
+    pub const RULE_ID : &'static str = "no-duplicate-contract-names";
+
+    pub(crate) fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        Ok(Box::new(NoDuplicateContractNames { data }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::imports::ImportGraph;
+    use crate::rules::project::tests::{contract, file};
+    use super::*;
+
+    #[test]
+    fn test_duplicate_contract_names() {
+        let content = "contract Token {}\ncontract Vault {}\n";
+        let files = vec![
+            file("src/A.sol", content, vec![contract("Token", "contract", false, "0:17:0"), contract("Vault", "contract", false, "18:17:0")]),
+            file("src/B.sol", content, vec![contract("Token", "interface", false, "0:17:0")]),
+            file("lib/C.sol", content, vec![contract("Vault", "contract", false, "18:17:0")]),
+        ];
+        let context_files = HashSet::from(["lib/C.sol".to_string()]);
+        let graph = ImportGraph::default();
+        let data = RuleEntry { id: NoDuplicateContractNames::RULE_ID.to_string(), severity: Severity::WARNING, data: vec![], options: None };
+        let rule = NoDuplicateContractNames { data };
+
+        let diags = rule.diagnose_project(&ProjectFiles::new(&files, &graph, &context_files));
+
+        let diags: Vec<(&str, u64, &str)> = diags.iter().map(|diag| (diag.uri.as_str(), diag.range.start.line, diag.message.as_str())).collect();
+        assert_eq!(diags, vec![
+            ("src/A.sol", 1, "\"Token\" is also defined in src/B.sol"),
+            ("src/A.sol", 2, "\"Vault\" is also defined in lib/C.sol"),
+            ("src/B.sol", 1, "\"Token\" is also defined in src/A.sol"),
+        ]);
+    }
+}
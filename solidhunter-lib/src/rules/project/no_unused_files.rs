@@ -0,0 +1,74 @@
+use crate::rules::types::*;
+use crate::rules::reporter::RuleReporter;
+use crate::types::*;
+use solc_wrapper::{ContractKind, SourceUnitChildNodes};
+
+/// Files no other file imports and that define no contract to deploy, so nothing uses them.
+/// Only meaningful when the whole project is linted, files outside of the linted ones aren't looked at.
+pub struct NoUnusedFiles {
+    data: RuleEntry
+}
+
+impl RuleType for NoUnusedFiles {
+
+    fn project_rule(&self) -> Option<&dyn ProjectRule> {
+        Some(self)
+    }
+}
+
+impl ProjectRule for NoUnusedFiles {
+
+    fn diagnose_project(&self, project: &ProjectFiles) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for file in project.project_files() {
+            let deployable = file.data.nodes.iter().any(|node| matches!(
+                node,
+                SourceUnitChildNodes::ContractDefinition(contract) if contract.contract_kind == ContractKind::Contract && !contract.is_abstract
+            ));
+            if deployable || !project.imports.importers(&file.path).is_empty() {
+                continue;
+            }
+            let mut reporter = RuleReporter::new(&self.data, file);
+            reporter.report_offset(0, 0, "File is not imported and has no contract to deploy");
+            res.append(&mut reporter.into_diags());
+        }
+        res
+    }
+}
+
+impl NoUnusedFiles {
+    pub const RULE_ID : &'static str = "no-unused-files";
+
+    pub(crate) fn create(data: RuleEntry) -> Result<Box<dyn RuleType>, RulesError> {
+        Ok(Box::new(NoUnusedFiles { data }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::imports::ImportGraph;
+    use crate::rules::project::tests::{contract, file};
+    use super::*;
+
+    #[test]
+    fn test_unused_files() {
+        let content = "contract A {}\n";
+        let files = vec![
+            file("Vault.sol", content, vec![contract("Vault", "contract", false, "0:13:0")]),
+            file("IVault.sol", content, vec![contract("IVault", "interface", false, "0:13:0")]),
+            file("Base.sol", content, vec![contract("Base", "contract", true, "0:13:0")]),
+            file("lib/Math.sol", content, vec![contract("Math", "library", false, "0:13:0")]),
+        ];
+        let context_files = HashSet::from(["lib/Math.sol".to_string()]);
+        let graph = ImportGraph::default();
+        let data = RuleEntry { id: NoUnusedFiles::RULE_ID.to_string(), severity: Severity::INFO, data: vec![], options: None };
+        let rule = NoUnusedFiles { data };
+
+        let diags = rule.diagnose_project(&ProjectFiles::new(&files, &graph, &context_files));
+
+        let uris: Vec<&str> = diags.iter().map(|diag| diag.uri.as_str()).collect();
+        assert_eq!(uris, vec!["IVault.sol", "Base.sol"]);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::fix::decode_src;
+use crate::linter::SolidFile;
+use crate::range_from_offset;
+use crate::rules::types::RuleEntry;
+use crate::types::*;
+
+/// The `source` of the diagnostics reported by the rules.
+pub const DIAG_SOURCE: &str = "solidhunter";
+
+/// Collects the diagnostics of a rule on a file.
+/// Each diagnostic gets the rule id as `code`, the configured severity, the source and the uri of the file,
+/// so rules only give a location and a message.
+pub struct RuleReporter<'a> {
+    rule: &'a RuleEntry,
+    file: &'a SolidFile,
+    diags: Vec<LintDiag>,
+}
+
+impl<'a> RuleReporter<'a> {
+    pub fn new(rule: &'a RuleEntry, file: &'a SolidFile) -> Self {
+        RuleReporter {
+            rule,
+            file,
+            diags: Vec::new(),
+        }
+    }
+
+    /// Reports `message` at `range`. The returned diagnostic can be completed, e.g. with a fix.
+    pub fn report(&mut self, range: Range, message: impl Into<String>) -> &mut LintDiag {
+        self.diags.push(LintDiag {
+            range,
+            severity: Some(self.rule.severity),
+            code: Some(NumberOrString::String(self.rule.id.clone())),
+            source: Some(DIAG_SOURCE.to_string()),
+            message: message.into(),
+            uri: self.file.path.clone(),
+            source_file_content: self.file.content.clone(),
+            fix: None,
+            suggestion: None,
+        });
+        self.diags.last_mut().expect("a diagnostic was just pushed")
+    }
+
+    /// Reports `message` on the `length` bytes starting at byte `offset` of the file.
+    pub fn report_offset(&mut self, offset: usize, length: usize, message: impl Into<String>) -> &mut LintDiag {
+        let range = range_from_offset(&self.file.content, offset, length);
+        self.report(range, message)
+    }
+
+    /// Reports `message` at the `src` location of an AST node (`start:length:file`).
+    /// A malformed location is reported at the start of the file.
+    pub fn report_src(&mut self, src: &str, message: impl Into<String>) -> &mut LintDiag {
+        let (offset, length) = decode_src(src).unwrap_or((0, 0));
+        self.report_offset(offset, length, message)
+    }
+
+    /// The diagnostics reported so far, in order.
+    pub fn diags_mut(&mut self) -> &mut [LintDiag] {
+        &mut self.diags
+    }
+
+    pub fn into_diags(self) -> Vec<LintDiag> {
+        self.diags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solc_wrapper::SourceUnit;
+
+    fn file(content: &str) -> SolidFile {
+        let data: SourceUnit = serde_json::from_value(serde_json::json!({
+            "id": 0,
+            "src": format!("0:{}:0", content.len()),
+            "nodeType": "SourceUnit",
+            "absolutePath": "Test.sol",
+            "exportedSymbols": {},
+            "nodes": [],
+        })).unwrap();
+
+        SolidFile {
+            data,
+            path: "Test.sol".to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_report_src() {
+        let file = file("pragma solidity 0.8.0;\ncontract a {}\n");
+        let rule = RuleEntry {
+            id: "contract-name-pascalcase".to_string(),
+            severity: Severity::ERROR,
+            data: vec![],
+            options: None,
+        };
+        let mut reporter = RuleReporter::new(&rule, &file);
+        reporter.report_src("32:1:0", "Contract name need to be in pascal case");
+        let diags = reporter.into_diags();
+
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, Some(NumberOrString::String("contract-name-pascalcase".to_string())));
+        assert_eq!(diags[0].severity, Some(Severity::ERROR));
+        assert_eq!(diags[0].source, Some(DIAG_SOURCE.to_string()));
+        assert_eq!(diags[0].uri, "Test.sol");
+        assert_eq!((diags[0].range.start.line, diags[0].range.start.character), (2, 9));
+        assert_eq!((diags[0].range.end.line, diags[0].range.end.character), (2, 10));
+    }
+}
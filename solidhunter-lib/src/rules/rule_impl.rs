@@ -1,65 +1,151 @@
 use std::collections::HashMap;
-use crate::rules::create_default_rules;
+use std::path::{Path, PathBuf};
+use schemars::gen::SchemaSettings;
+use serde_json::{json, Value};
+use crate::rules::{create_default_rules, create_options_schemas, create_rules};
 use crate::rules::types::*;
+use crate::types::Severity;
 
+pub const PRESET_PREFIX: &str = "solidhunter:";
 
-// Untested
-fn merge_rules(rules: &mut Vec<RuleEntry>, new_rules: &Vec<RuleEntry>) {
-    let mut new_rules_map = HashMap::new();
-    for rule in new_rules {
-        new_rules_map.insert(rule.id.clone(), rule);
+// Overrides the entries of `rules` by the ones of `new_rules` with the same id, and appends the others
+fn merge_rules(rules: &mut Vec<RuleEntry>, new_rules: Vec<RuleEntry>) {
+    for new_rule in new_rules {
+        match rules.iter_mut().find(|rule| rule.id == new_rule.id) {
+            Some(rule) => *rule = new_rule,
+            None => rules.push(new_rule),
+        }
     }
+}
 
-    for rule in rules {
-        if let Some(new_rule) = new_rules_map.get(&rule.id) {
-            rule.severity = new_rule.severity.clone();
-            rule.data = new_rule.data.clone();
+/// Returns the rules of a built-in preset, `recommended` or `all`.
+pub fn create_preset(name: &str) -> Option<Vec<RuleEntry>> {
+    match name {
+        "recommended" => Some(create_default_rules()),
+        "all" => {
+            let defaults: HashMap<String, RuleEntry> = create_default_rules()
+                .into_iter()
+                .map(|rule| (rule.id.clone(), rule))
+                .collect();
+            let mut ids: Vec<String> = create_rules().into_keys().collect();
+            ids.sort();
+            Some(ids.into_iter().map(|id| match defaults.get(&id) {
+                Some(rule) => rule.clone(),
+                None => RuleEntry { id, severity: Severity::WARNING, data: vec![], options: None },
+            }).collect())
         }
+        _ => None,
     }
 }
 
-pub fn create_rules_file(path: &str) {
-    let mut rules = Rules {
+pub fn create_rules_file(path: &str) -> Result<(), RulesError> {
+    let rules = Rules {
         name: "solidhunter".to_string(),
         includes: vec![],
         plugins: vec![],
         rules: create_default_rules(),
+        compiler: None,
     };
-    let serialized = serde_json::to_string_pretty(&rules).unwrap();
+    let serialized = serde_json::to_string_pretty(&rules).expect("the default rules are serializable");
 
-    std::fs::write(path, serialized).unwrap();
+    std::fs::write(path, serialized).map_err(|error| RulesError::IoError { path: path.to_string(), error })
+}
+
+/// Generates the JSON Schema of rules files.
+/// Each rule entry is described by its id, so that editors can check the options given to every rule.
+pub fn create_rules_schema() -> Value {
+    let mut gen = SchemaSettings::draft07().into_generator();
+    let options_schemas = create_options_schemas();
+    let severity = gen.subschema_for::<Severity>();
+
+    let mut ids: Vec<String> = create_rules().into_keys().collect();
+    ids.sort();
+    let entries: Vec<Value> = ids.into_iter().map(|id| {
+        let options = match options_schemas.get(&id) {
+            Some(builder) => json!(builder(&mut gen)),
+            None => json!({ "type": "object", "additionalProperties": false }),
+        };
+        json!({
+            "type": "object",
+            "required": ["id", "severity"],
+            "properties": {
+                "id": { "const": id },
+                "severity": severity,
+                "data": { "type": "array", "items": { "type": "string" } },
+                "options": options,
+            },
+        })
+    }).collect();
+
+    let mut schema = json!(gen.into_root_schema_for::<Rules>());
+    schema["definitions"]["RuleEntry"] = json!({ "oneOf": entries });
+    schema
 }
 
 type RulesResult = Result<Rules, RulesError>;
 
-pub fn parse_rules(path: &str) -> RulesResult {
-    let mut rules = Rules {
-        name: String::new(),
-        includes: Vec::new(),
-        plugins: Vec::new(),
-        rules: Vec::new(),
-    };
+// `stack` holds the files being parsed, from the root one to the current one
+fn parse_rules_file(path: &Path, stack: &mut Vec<PathBuf>) -> RulesResult {
+    let display_path = path.to_string_lossy().to_string();
+    let io_error = |error: std::io::Error| RulesError::IoError { path: display_path.clone(), error };
 
-    if !std::path::Path::new(&path).is_file() {
-        return Err(RulesError::IoError(std::io::Error::new(std::io::ErrorKind::NotFound, "Rules file not found")));
+    if !path.is_file() {
+        return Err(RulesError::NotFound { path: display_path });
     }
-    let file = std::fs::read_to_string(path).unwrap();
-    let parsed: Rules = serde_json::from_str(&file).unwrap();
-
-    /*
-    // Danger zone
-    for include in parsed.includes {
-        let include_rules = parse_rules(include.as_str());
-        merge_rules(&mut rules.rules, &include_rules.unwrap().rules);
+    let path = path.canonicalize().map_err(io_error)?;
+    if stack.contains(&path) {
+        let mut cycle: Vec<String> = stack.iter().map(|file| file.to_string_lossy().to_string()).collect();
+        cycle.push(path.to_string_lossy().to_string());
+        return Err(RulesError::IncludeCycle(cycle));
     }
+    let file = std::fs::read_to_string(&path).map_err(io_error)?;
+    let mut parsed: Rules = serde_json::from_str(&file).map_err(|e| RulesError::ParseError {
+        path: display_path.clone(),
+        line: e.line(),
+        column: e.column(),
+        // The location is already given, don't repeat it in the message
+        message: e.to_string().trim_end_matches(&format!(" at line {} column {}", e.line(), e.column())).to_string(),
+    })?;
 
-    merge_rules(&mut rules.rules, &parsed.rules);
-    // End of danger zone
-     */
+    // Includes are applied in order, each one overriding the previous ones, then the rules of this file
+    stack.push(path.clone());
+    let base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    if let Some(solc) = parsed.compiler.as_mut().and_then(|compiler| compiler.solc.as_mut()) {
+        *solc = base_dir.join(&*solc).to_string_lossy().to_string();
+    }
+    let mut rules: Vec<RuleEntry> = Vec::new();
+    let mut compiler: Option<CompilerConfig> = None;
+    for include in &parsed.includes {
+        let included = match include.strip_prefix(PRESET_PREFIX) {
+            Some(preset) => create_preset(preset).ok_or_else(|| RulesError::UnknownPreset {
+                path: display_path.clone(),
+                name: include.clone(),
+            })?,
+            None => match parse_rules_file(&base_dir.join(include), stack) {
+                Ok(included) => {
+                    compiler = included.compiler.or(compiler);
+                    included.rules
+                }
+                Err(RulesError::NotFound { .. }) => return Err(RulesError::IncludeNotFound {
+                    path: display_path.clone(),
+                    include: include.clone(),
+                }),
+                Err(e) => return Err(e),
+            },
+        };
+        merge_rules(&mut rules, included);
+    }
+    stack.pop();
 
+    merge_rules(&mut rules, parsed.rules);
+    parsed.rules = rules;
+    parsed.compiler = parsed.compiler.or(compiler);
     Ok(parsed)
 }
 
+pub fn parse_rules(path: &str) -> RulesResult {
+    parse_rules_file(Path::new(path), &mut Vec::new())
+}
 
 // create rules
 /*
@@ -84,4 +170,74 @@ macro_rules! create_rule {
             }
         }
     };
-}*/
\ No newline at end of file
+}*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::best_practises::line_maxlen::LineMaxLenOptions;
+    use crate::rules::best_practises::reason_string::ReasonStringOptions;
+
+    #[test]
+    fn test_parse_rules_includes() {
+        let path = "tests/files/rules/includes/.solidhunter.json";
+
+        let rules = parse_rules(path).unwrap().rules;
+        let rule = |id: &str| rules.iter().find(|rule| rule.id == id).unwrap();
+
+        assert_eq!(rules.len(), create_default_rules().len());
+        assert_eq!(rule("quotes").severity, Severity::WARNING);
+        assert_eq!(rule("line-max-len").severity, Severity::ERROR);
+        assert_eq!(rule("line-max-len").data, vec!["120"]);
+        assert_eq!(parse_rules(path).unwrap().compiler, Some(CompilerConfig { offline: true, ..CompilerConfig::default() }));
+    }
+
+    #[test]
+    fn test_parse_rules_options() {
+        let rules = parse_rules("tests/files/rules/options.json").unwrap().rules;
+        let reason_string: ReasonStringOptions = rules[0].options().unwrap();
+        let line_max_len: LineMaxLenOptions = rules[1].options().unwrap();
+
+        assert_eq!(reason_string.max_length, 64);
+        assert_eq!(line_max_len.max_length, 120);
+    }
+
+    #[test]
+    fn test_create_rules_schema() {
+        let schema = create_rules_schema();
+        let entries = schema["definitions"]["RuleEntry"]["oneOf"].as_array().unwrap();
+        let entry = entries.iter().find(|entry| entry["properties"]["id"]["const"] == "reason-string").unwrap();
+        let options = entry["properties"]["options"]["$ref"].as_str().unwrap();
+        let options = &schema["definitions"][options.trim_start_matches("#/definitions/")];
+
+        assert_eq!(entries.len(), create_rules().len());
+        assert_eq!(options["properties"]["maxLength"]["default"], 32);
+    }
+
+    #[test]
+    fn test_parse_rules_include_cycle() {
+        match parse_rules("tests/files/rules/cycle/b.json") {
+            Err(RulesError::IncludeCycle(cycle)) => assert_eq!(cycle.len(), 3),
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_parse_rules_invalid_json() {
+        match parse_rules("tests/files/rules/invalid.json") {
+            Err(RulesError::ParseError { line, column, .. }) => assert_eq!((line, column), (3, 13)),
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_parse_rules_not_found() {
+        assert!(matches!(parse_rules("tests/files/rules/none.json"), Err(RulesError::NotFound { .. })));
+        assert!(matches!(parse_rules("tests/files/rules/include_not_found.json"), Err(RulesError::IncludeNotFound { .. })));
+    }
+
+    #[test]
+    fn test_parse_rules_unknown_preset() {
+        assert!(matches!(parse_rules("tests/files/rules/unknown_preset.json"), Err(RulesError::UnknownPreset { .. })));
+    }
+}
@@ -1,30 +1,171 @@
+use std::collections::HashSet;
+use schemars::JsonSchema;
 use serde::{Serialize, Deserialize};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use solc_wrapper::ast::visitor::Visitor;
+use thiserror::Error;
 use crate::linter::SolidFile;
+use crate::imports::ImportGraph;
 use crate::types::*;
 
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct RuleEntry
 {
     pub id: String,
     pub severity: Severity,
+    /// Positional options, kept for compatibility with the first rules file format.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub data: Vec<String>,
+    /// Named options of the rule, e.g. `{ "maxLength": 64 }`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<Value>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Options of a rule, read from the `options` object of its entry or from its positional `data`.
+/// Implementors should be `#[serde(default)]` so that every option is optional.
+pub trait RuleOptions: Serialize + DeserializeOwned + JsonSchema + Default {
+    /// The names of the options given by the positional `data` values, in order.
+    const POSITIONAL: &'static [&'static str];
+}
+
+impl RuleEntry {
+    /// Reads the options of the rule, `options` taking precedence over `data`.
+    pub fn options<T: RuleOptions>(&self) -> Result<T, RulesError> {
+        let invalid = |message: String| RulesError::InvalidRuleOptions { id: self.id.clone(), message };
+
+        let value = match &self.options {
+            Some(options) => options.clone(),
+            None if self.data.is_empty() => return Ok(T::default()),
+            None => {
+                if self.data.len() > T::POSITIONAL.len() {
+                    return Err(invalid(format!("expected at most {} values, got {}", T::POSITIONAL.len(), self.data.len())));
+                }
+                let options = T::POSITIONAL.iter().zip(&self.data).map(|(name, value)| {
+                    // "64" is read as a number and "true" as a boolean, anything else as a string
+                    let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.clone()));
+                    (name.to_string(), value)
+                });
+                Value::Object(options.collect())
+            }
+        };
+        serde_json::from_value(value).map_err(|e| invalid(e.to_string()))
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 pub struct Rules {
     pub name: String,
+    /// Rules files or presets (`solidhunter:recommended`, `solidhunter:all`) this file builds upon.
+    /// Paths are relative to the including file.
+    #[serde(default, alias = "extends")]
     pub includes: Vec<String>,
+    #[serde(default)]
     pub plugins: Vec<String>,
-    pub rules: Vec<RuleEntry>
+    pub rules: Vec<RuleEntry>,
+    /// Where the solc binaries come from, taken from the included files when not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiler: Option<CompilerConfig>,
 }
 
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CompilerConfig {
+    /// Only use the installed compilers, never download one.
+    #[serde(default)]
+    pub offline: bool,
+    /// A solc binary, or a directory of binaries, used instead of the ones managed by svm.
+    /// Relative to the rules file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solc: Option<String>,
+    /// Version requirement, as in a `pragma solidity`, for the files without pragma.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_version: Option<String>,
+}
+
+fn suggestion_hint(suggestion: &Option<String>) -> String {
+    match suggestion {
+        Some(suggestion) => format!(", did you mean '{}'?", suggestion),
+        None => String::new(),
+    }
+}
+
+#[derive(Error, Debug)]
 pub enum RulesError {
-    IoError(std::io::Error),
+    #[error("{path}: rules file not found")]
+    NotFound { path: String },
+    #[error("{path}: included rules file '{include}' not found")]
+    IncludeNotFound { path: String, include: String },
+    #[error("{path}: {error}")]
+    IoError { path: String, #[source] error: std::io::Error },
+    #[error("{path}:{line}:{column}: {message}")]
+    ParseError { path: String, line: usize, column: usize, message: String },
+    #[error("unknown rule '{id}'{}", suggestion_hint(.suggestion))]
+    UnknownRule { id: String, suggestion: Option<String> },
+    #[error("invalid options for rule '{id}': {message}")]
+    InvalidRuleOptions { id: String, message: String },
+    #[error("{path}: unknown preset '{name}'")]
+    UnknownPreset { path: String, name: String },
+    #[error("include cycle: {}", .0.join(" -> "))]
+    IncludeCycle(Vec<String>),
 }
 
 pub trait RuleType: Send + Sync + 'static {
 
-    fn diagnose(&self, file: &SolidFile, files: &Vec<SolidFile>) -> Vec<LintDiag>;
+    fn diagnose(&self, _file: &SolidFile, _files: &Vec<SolidFile>) -> Vec<LintDiag> {
+        Vec::new()
+    }
+
+    /// Rules looking at the imports of a file get the import graph. Its diagnostics are added to the ones of `diagnose`.
+    fn diagnose_imports(&self, _file: &SolidFile, _files: &Vec<SolidFile>, _imports: &ImportGraph) -> Vec<LintDiag> {
+        Vec::new()
+    }
+
+    /// Rules looking at the content of the imported files, directly or not, have them loaded among `files`.
+    /// They aren't otherwise, as loading them runs solc on each of them.
+    fn needs_imported_files(&self) -> bool {
+        false
+    }
+
+    /// Rules checking AST nodes can return a visitor, run along with the ones of the other rules in a single walk
+    /// of the file. Its diagnostics are added to the ones of `diagnose`.
+    fn visitor<'a>(&'a self, _file: &'a SolidFile) -> Option<Box<dyn RuleVisitor + 'a>> {
+        None
+    }
+
+    /// Project rules return themselves, so that the linter runs them once over all the files.
+    fn project_rule(&self) -> Option<&dyn ProjectRule> {
+        None
+    }
+}
+
+/// Rules comparing the files of the project, run once after all of them are loaded rather than per file.
+/// They are configured and created like the other rules, as `RuleType`s returning themselves from `project_rule`.
+pub trait ProjectRule {
+    /// Diagnoses the project files. Only the diagnostics of the linted files are kept.
+    fn diagnose_project(&self, project: &ProjectFiles) -> Vec<LintDiag>;
+}
+
+/// The files a project rule looks at.
+pub struct ProjectFiles<'a> {
+    /// Every loaded file, the ones only loaded because others import them included.
+    pub files: &'a [SolidFile],
+    pub imports: &'a ImportGraph,
+    context_files: &'a HashSet<String>,
+}
+
+impl<'a> ProjectFiles<'a> {
+    pub fn new(files: &'a [SolidFile], imports: &'a ImportGraph, context_files: &'a HashSet<String>) -> Self {
+        ProjectFiles { files, imports, context_files }
+    }
+
+    /// The files given to the linter, as opposed to the imported dependencies.
+    pub fn project_files(&self) -> impl Iterator<Item = &'a SolidFile> + '_ {
+        self.files.iter().filter(|file| !self.context_files.contains(&file.path))
+    }
+}
+
+pub trait RuleVisitor: Visitor {
+    fn into_diags(self: Box<Self>) -> Vec<LintDiag>;
 }
\ No newline at end of file
@@ -0,0 +1,268 @@
+use std::collections::HashSet;
+use crate::rules::reporter::DIAG_SOURCE;
+use crate::types::*;
+
+const DIRECTIVE_PREFIX: &str = "solidhunter-";
+pub const UNUSED_SUPPRESSION_CODE: &str = "unused-suppression";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SuppressionKind {
+    Disable,
+    Enable,
+    DisableLine,
+    DisableNextLine,
+}
+
+impl SuppressionKind {
+    fn from_keyword(keyword: &str) -> Option<SuppressionKind> {
+        match keyword {
+            "disable" => Some(SuppressionKind::Disable),
+            "enable" => Some(SuppressionKind::Enable),
+            "disable-line" => Some(SuppressionKind::DisableLine),
+            "disable-next-line" => Some(SuppressionKind::DisableNextLine),
+            _ => None,
+        }
+    }
+
+    fn keyword(&self) -> &'static str {
+        match self {
+            SuppressionKind::Disable => "disable",
+            SuppressionKind::Enable => "enable",
+            SuppressionKind::DisableLine => "disable-line",
+            SuppressionKind::DisableNextLine => "disable-next-line",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Suppression {
+    pub kind: SuppressionKind,
+    /// The ids of the rules concerned by the directive, every rule if empty.
+    pub rules: Vec<String>,
+    /// The location of the directive in the file.
+    pub range: Range,
+}
+
+impl Suppression {
+    fn concerns(&self, rule_id: &str) -> bool {
+        self.rules.is_empty() || self.rules.iter().any(|rule| rule == rule_id)
+    }
+
+    // The key used to track whether the directive (or one of its rule ids) suppressed something
+    fn slot(&self, idx: usize, rule_id: &str) -> (usize, Option<String>) {
+        if self.rules.is_empty() {
+            (idx, None)
+        } else {
+            (idx, Some(rule_id.to_string()))
+        }
+    }
+}
+
+/// Parses the `solidhunter-disable`, `solidhunter-enable`, `solidhunter-disable-line` and
+/// `solidhunter-disable-next-line` comments of a file, in the order they appear.
+pub fn parse_suppressions(content: &str) -> Vec<Suppression> {
+    let mut res = Vec::new();
+
+    for (line_idx, line) in content.lines().enumerate() {
+        let comment_start = match (line.find("//"), line.find("/*")) {
+            (Some(a), Some(b)) => a.min(b),
+            (Some(a), None) | (None, Some(a)) => a,
+            (None, None) => continue,
+        };
+        let directive_start = match line[comment_start..].find(DIRECTIVE_PREFIX) {
+            Some(idx) => comment_start + idx,
+            None => continue,
+        };
+        let directive = line[directive_start + DIRECTIVE_PREFIX.len()..].split("*/").next().unwrap_or("");
+        let keyword_end = directive.find(char::is_whitespace).unwrap_or(directive.len());
+        let kind = match SuppressionKind::from_keyword(&directive[..keyword_end]) {
+            Some(kind) => kind,
+            None => continue,
+        };
+        let rules = directive[keyword_end..]
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|rule| !rule.is_empty())
+            .map(|rule| rule.to_string())
+            .collect();
+        let length = (DIRECTIVE_PREFIX.len() + keyword_end) as u64;
+
+        res.push(Suppression {
+            kind,
+            rules,
+            range: Range {
+                start: Position { line: line_idx as u64 + 1, character: directive_start as u64 },
+                end: Position { line: line_idx as u64 + 1, character: directive_start as u64 + length },
+                length,
+            },
+        });
+    }
+    res
+}
+
+// Finds the directive suppressing a diagnostic of `rule_id` starting at `start`, if any
+fn find_suppression(suppressions: &[Suppression], rule_id: &str, start: &Position) -> Option<(usize, Option<String>)> {
+    let line_level = suppressions.iter().enumerate().find(|(_, suppression)| {
+        let line = suppression.range.start.line;
+        let target = match suppression.kind {
+            SuppressionKind::DisableLine => line,
+            SuppressionKind::DisableNextLine => line + 1,
+            _ => return false,
+        };
+        target == start.line && suppression.concerns(rule_id)
+    });
+    if let Some((idx, suppression)) = line_level {
+        return Some(suppression.slot(idx, rule_id));
+    }
+
+    // Replay the block directives placed before the diagnostic, the last one concerning the rule wins
+    let mut res = None;
+    for (idx, suppression) in suppressions.iter().enumerate() {
+        let pos = &suppression.range.start;
+        if (pos.line, pos.character) >= (start.line, start.character) {
+            break;
+        }
+        if !suppression.concerns(rule_id) {
+            continue;
+        }
+        match suppression.kind {
+            SuppressionKind::Disable => res = Some(suppression.slot(idx, rule_id)),
+            SuppressionKind::Enable => res = None,
+            _ => {}
+        }
+    }
+    res
+}
+
+fn unused_suppression_diag(suppression: &Suppression, rule_id: Option<&String>, uri: &str, content: &str) -> LintDiag {
+    let message = match rule_id {
+        Some(rule_id) => format!("Unused solidhunter-{} directive for rule '{}'", suppression.kind.keyword(), rule_id),
+        None => format!("Unused solidhunter-{} directive", suppression.kind.keyword()),
+    };
+
+    LintDiag {
+        range: suppression.range.clone(),
+        severity: Some(Severity::WARNING),
+        code: Some(NumberOrString::String(UNUSED_SUPPRESSION_CODE.to_string())),
+        source: Some(DIAG_SOURCE.to_string()),
+        message,
+        uri: uri.to_string(),
+        source_file_content: content.to_string(),
+        fix: None,
+        suggestion: None,
+    }
+}
+
+/// Removes the diagnostics silenced by a suppression comment of `content`.
+/// Diagnostics are matched on their code, which holds the id of the rule reporting them.
+/// When `report_unused` is set, a diagnostic is added for every directive (or rule id of a directive)
+/// that silenced nothing.
+pub fn apply_suppressions(content: &str, uri: &str, diags: Vec<LintDiag>, report_unused: bool) -> Vec<LintDiag> {
+    let suppressions = parse_suppressions(content);
+    if suppressions.is_empty() {
+        return diags;
+    }
+    let mut used: HashSet<(usize, Option<String>)> = HashSet::new();
+    let mut res: Vec<LintDiag> = Vec::new();
+
+    for diag in diags {
+        let rule_id = match &diag.code {
+            Some(NumberOrString::String(code)) => code.clone(),
+            Some(NumberOrString::Number(code)) => code.to_string(),
+            None => String::new(),
+        };
+        match find_suppression(&suppressions, &rule_id, &diag.range.start) {
+            Some(slot) => {
+                used.insert(slot);
+            }
+            None => res.push(diag),
+        }
+    }
+
+    if report_unused {
+        for (idx, suppression) in suppressions.iter().enumerate() {
+            if suppression.kind == SuppressionKind::Enable {
+                continue;
+            }
+            if suppression.rules.is_empty() {
+                if !used.contains(&(idx, None)) {
+                    res.push(unused_suppression_diag(suppression, None, uri, content));
+                }
+                continue;
+            }
+            for rule_id in &suppression.rules {
+                if !used.contains(&(idx, Some(rule_id.clone()))) {
+                    res.push(unused_suppression_diag(suppression, Some(rule_id), uri, content));
+                }
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diag(rule_id: &str, line: u64, character: u64) -> LintDiag {
+        LintDiag {
+            range: Range {
+                start: Position { line, character },
+                end: Position { line, character: character + 1 },
+                length: 1,
+            },
+            severity: Some(Severity::WARNING),
+            code: Some(NumberOrString::String(rule_id.to_string())),
+            source: None,
+            message: String::new(),
+            uri: String::new(),
+            source_file_content: String::new(),
+            fix: None,
+            suggestion: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_suppressions() {
+        let content = "// solidhunter-disable-next-line quotes, func-name-camelcase\nuint a; /* solidhunter-disable-line */\n// solidhunter-unknown\n";
+        let res = parse_suppressions(content);
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].kind, SuppressionKind::DisableNextLine);
+        assert_eq!(res[0].rules, vec!["quotes", "func-name-camelcase"]);
+        assert_eq!(res[0].range.start.line, 1);
+        assert_eq!(res[1].kind, SuppressionKind::DisableLine);
+        assert!(res[1].rules.is_empty());
+        assert_eq!(res[1].range.start.character, 11);
+    }
+
+    #[test]
+    fn test_line_suppressions() {
+        let content = "// solidhunter-disable-next-line quotes\nstring a = 'a';\nstring b = 'b'; // solidhunter-disable-line\n";
+        let diags = vec![diag("quotes", 2, 11), diag("other", 2, 0), diag("quotes", 3, 11)];
+        let res = apply_suppressions(content, "", diags, false);
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].code, Some(NumberOrString::String("other".to_string())));
+    }
+
+    #[test]
+    fn test_block_suppressions() {
+        let content = "// solidhunter-disable\nA\n// solidhunter-enable quotes\nB\n// solidhunter-enable\nC\n";
+        let diags = vec![diag("quotes", 2, 0), diag("quotes", 4, 0), diag("other", 4, 0), diag("other", 6, 0)];
+        let res = apply_suppressions(content, "", diags, false);
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].range.start.line, 4);
+        assert_eq!(res[1].range.start.line, 6);
+    }
+
+    #[test]
+    fn test_unused_suppressions() {
+        let content = "// solidhunter-disable-next-line quotes, other\nstring a = 'a';\n";
+        let res = apply_suppressions(content, "", vec![diag("quotes", 2, 11)], true);
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].code, Some(NumberOrString::String(UNUSED_SUPPRESSION_CODE.to_string())));
+        assert_eq!(res[0].message, "Unused solidhunter-disable-next-line directive for rule 'other'");
+    }
+}
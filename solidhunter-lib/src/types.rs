@@ -1,13 +1,14 @@
 use serde::{Serialize, Deserialize};
+use schemars::JsonSchema;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum LintError {
-    #[error("LintError: Solc error occured")]
+    #[error("LintError: Solc error occured: {0}")]
     SolcError(#[from] solc_wrapper::SolcError),
     #[error("LintError: Something went wrong with the file")]
     IoError(#[from] std::io::Error),
-    #[error("LintError: Something went wrong")]
+    #[error("LintError: {0}")]
     LinterError(String),
 }
 
@@ -41,7 +42,16 @@ pub struct LintDiag {
     pub uri: Uri,
 
     #[serde(rename="sourceFileContent")]
-    pub source_file_content: String
+    pub source_file_content: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The edits fixing this diagnostic. They are applied all together or not at all.
+    pub fix: Option<Vec<TextEdit>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    /// Edits of a fix that may break code outside of the file, e.g. the callers of a renamed public function.
+    /// They are only proposed, never applied by `--fix`.
+    pub suggestion: Option<Vec<TextEdit>>,
 }
 
 
@@ -55,7 +65,7 @@ pub struct Position {
     pub character: u64,
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Serialize, Deserialize, JsonSchema, Debug)]
 pub enum Severity {
     /// Reports an error.
     ERROR = 1,
@@ -76,6 +86,17 @@ pub struct Range {
 
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TextEdit {
+    /// The range of the text to be replaced.
+    pub range: Range,
+
+    /// The string to be inserted. For delete operations use an
+    /// empty string.
+    #[serde(rename="newText")]
+    pub new_text: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub enum NumberOrString {
     Number(i32),
     String(String),
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract vault {
+    function Deposit() public {}
+
+    function _Withdraw() internal {}
+
+    function _Transfer() private {}
+
+    function run() public {
+        uint Deposit = 1;
+        Deposit;
+        _Withdraw();
+        _Transfer();
+        this.Deposit();
+    }
+}
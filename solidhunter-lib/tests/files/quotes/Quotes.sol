@@ -0,0 +1,9 @@
+pragma solidity ^0.8.0;
+
+/// @notice Alice's and Bob's greetings
+contract Quotes {
+    // The 'single' quotes of a comment
+    string public greeting = 'hello';
+    string public quoted = "it's";
+    string public mixed = 'say "hi"';
+}
@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import "./Token.sol";
+import "./Missing.sol";
+import "@missing/Token.sol";
+
+contract Importer {}
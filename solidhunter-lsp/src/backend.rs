@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use solidhunter_lib::linter::SolidLinter;
+use solidhunter_lib::project::Project;
+use solidhunter_lib::rules::types::RulesError;
+use tokio::sync::Mutex;
+use tower_lsp::jsonrpc::Result;
+use tower_lsp::lsp_types::*;
+use tower_lsp::{Client, LanguageServer};
+
+use crate::utils::to_lsp_diagnostic;
+
+const RULES_FILE: &str = ".solidhunter.json";
+
+pub struct Backend {
+    client: Client,
+    // An async lock, as linting holds it while solc runs
+    linter: Arc<Mutex<SolidLinter>>,
+}
+
+impl Backend {
+    pub fn new(client: Client) -> Self {
+        Backend {
+            client,
+            linter: Arc::new(Mutex::new(SolidLinter::new())),
+        }
+    }
+
+    fn rules_file(params: &InitializeParams) -> String {
+        let root = params.root_uri.as_ref().and_then(|uri| uri.to_file_path().ok());
+
+        match root {
+            Some(root) => root.join(RULES_FILE).to_string_lossy().to_string(),
+            None => RULES_FILE.to_string(),
+        }
+    }
+
+    fn to_path(uri: &Url) -> String {
+        match uri.to_file_path() {
+            Ok(path) => path.to_string_lossy().to_string(),
+            Err(_) => uri.to_string(),
+        }
+    }
+
+    async fn lint(&self, uri: Url, content: String, version: Option<i32>) {
+        let path = Self::to_path(&uri);
+        let linter = self.linter.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            linter.blocking_lock().parse_content(path, &content)
+        })
+        .await;
+
+        match result {
+            // Parsing errors are diagnostics of the file, reported along with the ones of the rules
+            Ok(result) => match result {
+                Ok(diags) => self.client.publish_diagnostics(uri, diags.iter().map(to_lsp_diagnostic).collect(), version).await,
+                Err(e) => self.client.log_message(MessageType::ERROR, format!("{:?}", e)).await,
+            },
+            Err(e) => self.client.log_message(MessageType::ERROR, e.to_string()).await,
+        }
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        let rules_file = Self::rules_file(&params);
+        let mut linter = self.linter.lock().await;
+        match linter.initalize(&rules_file) {
+            Ok(()) => {}
+            Err(RulesError::NotFound { .. }) => {
+                linter.initalize_with_default_rules();
+                self.client.log_message(MessageType::INFO, format!("{} not found, using the default rules", rules_file)).await;
+            }
+            // Keep linting with the default rules until the file is fixed
+            Err(e) => {
+                linter.initalize_with_default_rules();
+                self.client.show_message(MessageType::ERROR, format!("Invalid rules file: {}", e)).await;
+            }
+        }
+        // The imports are resolved like in the Foundry or Hardhat project of the workspace
+        let root = params.root_uri.as_ref().and_then(|uri| uri.to_file_path().ok());
+        match root.map(|root| Project::detect(&root)) {
+            Some(Ok(Some(project))) => linter.set_compile_options(project.compile_options()),
+            Some(Err(e)) => self.client.show_message(MessageType::ERROR, e.to_string()).await,
+            _ => {}
+        }
+
+        Ok(InitializeResult {
+            server_info: Some(ServerInfo {
+                name: env!("CARGO_PKG_NAME").to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            }),
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+                ..ServerCapabilities::default()
+            },
+        })
+    }
+
+    async fn initialized(&self, _: InitializedParams) {
+        self.client.log_message(MessageType::INFO, "SolidHunter language server initialized").await;
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let doc = params.text_document;
+        self.lint(doc.uri, doc.text, Some(doc.version)).await;
+    }
+
+    async fn did_change(&self, mut params: DidChangeTextDocumentParams) {
+        // Full synchronization: the last change holds the whole document
+        if let Some(change) = params.content_changes.pop() {
+            let doc = params.text_document;
+            self.lint(doc.uri, change.text, Some(doc.version)).await;
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        let path = Self::to_path(&uri);
+        let linter = self.linter.clone();
+        // A file still imported by an open one is reloaded from the disk
+        let result = tokio::task::spawn_blocking(move || linter.blocking_lock().delete_file(path)).await;
+        if let Err(e) = result {
+            self.client.log_message(MessageType::ERROR, e.to_string()).await;
+        }
+        self.client.publish_diagnostics(uri, vec![], None).await;
+    }
+}
@@ -0,0 +1,66 @@
+use solc_wrapper::ast::ast::get_line_from_offset;
+use solidhunter_lib::offset_from_range;
+use solidhunter_lib::types::{LintDiag, NumberOrString, Severity};
+use tower_lsp::lsp_types::{self, Diagnostic, DiagnosticSeverity, Position, Range};
+
+// LintDiag lines are 1-based while LSP positions are 0-based
+fn to_lsp_position(line: usize, character: usize) -> Position {
+    Position {
+        line: line.saturating_sub(1) as u32,
+        character: character as u32,
+    }
+}
+
+pub fn to_lsp_range(content: &str, range: &solidhunter_lib::types::Range) -> Range {
+    let start = to_lsp_position(range.start.line as usize, range.start.character as usize);
+    let end_offset = offset_from_range(content, range) + range.length as usize;
+    let (line, character) = get_line_from_offset(content, end_offset.min(content.len()));
+
+    Range {
+        start,
+        end: to_lsp_position(line, character),
+    }
+}
+
+fn to_lsp_severity(severity: Severity) -> DiagnosticSeverity {
+    match severity {
+        Severity::ERROR => DiagnosticSeverity::ERROR,
+        Severity::WARNING => DiagnosticSeverity::WARNING,
+        Severity::INFO => DiagnosticSeverity::INFORMATION,
+        Severity::HINT => DiagnosticSeverity::HINT,
+    }
+}
+
+pub fn to_lsp_diagnostic(diag: &LintDiag) -> Diagnostic {
+    Diagnostic {
+        range: to_lsp_range(&diag.source_file_content, &diag.range),
+        severity: diag.severity.map(to_lsp_severity),
+        code: diag.code.as_ref().map(|code| match code {
+            NumberOrString::Number(n) => lsp_types::NumberOrString::Number(*n),
+            NumberOrString::String(s) => lsp_types::NumberOrString::String(s.clone()),
+        }),
+        source: Some(diag.source.clone().unwrap_or_else(|| "solidhunter".to_string())),
+        message: diag.message.clone(),
+        ..Diagnostic::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solidhunter_lib::types;
+
+    #[test]
+    fn test_to_lsp_range_single_line() {
+        let content = "pragma solidity 0.8.0;\ncontract Test {}\n";
+        let range = types::Range {
+            start: types::Position { line: 2, character: 9 },
+            end: types::Position { line: 2, character: 13 },
+            length: 4,
+        };
+        let res = to_lsp_range(content, &range);
+
+        assert_eq!(res.start, Position { line: 1, character: 9 });
+        assert_eq!(res.end, Position { line: 1, character: 13 });
+    }
+}
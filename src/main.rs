@@ -1,99 +1,355 @@
-use clap::Parser;
-use colored::Colorize;
-use solidhunter_lib::linter::SolidLinter;
-use solidhunter_lib::offset_from_range;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use clap::{Parser, ValueEnum};
+use solidhunter_lib::fix::FixReport;
+use solidhunter_lib::linter::{AstMode, CacheConfig, CompileOptions, SolidLinter, DEFAULT_CACHE_DIR};
+use solidhunter_lib::project::Project;
 
-use solidhunter_lib::rules::rule_impl::{create_rules_file, parse_rules};
-use solidhunter_lib::types::Severity;
+use solidhunter_lib::rules::rule_impl::{create_rules_file, create_rules_schema};
+use solidhunter_lib::rules::types::RulesError;
+use solidhunter_lib::types::{LintError, LintResult, Severity};
+use report::{create_diags_json_reporter, create_reporter, write_diag, write_diff, FormatOutput, OutputFormat, Reporter, Summary};
+
+mod report;
+mod sarif;
+
+/// Exit code of the process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    /// No finding at or above the `--fail-on` severity, nor more warnings than `--max-warnings`.
+    Clean = 0,
+    Findings = 1,
+    /// Invalid rules file or command line.
+    ConfigError = 2,
+    /// A file couldn't be linted, or solc reported errors for it.
+    CompilerError = 3,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum FailOn {
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl From<FailOn> for Severity {
+    fn from(fail_on: FailOn) -> Self {
+        match fail_on {
+            FailOn::Error => Severity::ERROR,
+            FailOn::Warning => Severity::WARNING,
+            FailOn::Info => Severity::INFO,
+            FailOn::Hint => Severity::HINT,
+        }
+    }
+}
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    #[arg(short = 'p', long = "path", default_value = ".", help = "Specify project path")]
+    #[arg(short = 'p', long = "path", help = "Specify project path, the sources of the detected Foundry or Hardhat project or . by default")]
     project_path: Vec<String>,
 
+    #[arg(long = "no-project", default_value = "false", help = "Don't detect Foundry and Hardhat projects, nor use their layout and remappings")]
+    no_project: bool,
+
     #[arg(short = 'f', long = "file", default_value = "", help = "Specify a single file to lint")]
     file_to_lint: String,
 
     #[arg(short = 'e', long = "exclude", help = "Exclude part of the project path")]
     ignore_path: Vec<String>,
 
+    #[arg(long = "no-gitignore", default_value = "false", help = "Lint the files ignored by .gitignore files")]
+    no_gitignore: bool,
+
     #[arg(short = 'r', long = "rules", default_value = ".solidhunter.json", help = "Specify rules file")]
     rules_file: String,
 
-    #[arg(short = 'j', long = "json_output", default_value = "false", help = "Outputs a json format instead")]
+    #[arg(short = 'j', long = "json_output", default_value = "false", help = "Outputs the diagnostics of all the files as a single json array instead")]
     to_json: bool,
 
+    #[arg(long = "format", value_name = "FORMAT[=FILE]", value_parser = FormatOutput::parse, default_value = "text", help = "Output format, written to FILE instead of the standard output when given, can be repeated to output several formats [possible values: text, json, jsonl, sarif]")]
+    formats: Vec<FormatOutput>,
+
+    #[arg(long = "fail-on", value_enum, default_value = "error", help = "Exit with 1 when a finding is at least this severe")]
+    fail_on: FailOn,
+
+    #[arg(long = "max-warnings", help = "Exit with 1 when there are more warnings")]
+    max_warnings: Option<usize>,
+
     #[arg(short = 'v', long = "verbose", default_value = "false", help = "Verbose output")]
     verbose: bool,
 
     #[arg(short = 'i', long = "init", default_value = "false", help = "Initialize rules file")]
     init: bool,
+
+    #[arg(long = "schema", default_value = "false", help = "Print the JSON Schema of rules files")]
+    schema: bool,
+
+    #[arg(long = "fix", default_value = "false", help = "Apply the available fixes to the linted files")]
+    fix: bool,
+
+    #[arg(long = "fix-dry-run", default_value = "false", help = "Show the available fixes without writing the files")]
+    fix_dry_run: bool,
+
+    #[arg(long = "jobs", default_value = "0", help = "Number of files linted in parallel, 0 to use one thread per CPU")]
+    jobs: usize,
+
+    #[arg(long = "report-unused-suppressions", default_value = "false", help = "Report the suppression comments silencing nothing")]
+    report_unused_suppressions: bool,
+
+    #[arg(long = "analyse", default_value = "false", help = "Compile the files up to the analysis, for typed and resolved ASTs")]
+    analyse: bool,
+
+    #[arg(long = "base-path", help = "Root of the source tree used to resolve the imports when analysing")]
+    base_path: Option<String>,
+
+    #[arg(long = "include-path", help = "Additional root used to resolve the imports when analysing")]
+    include_paths: Vec<String>,
+
+    #[arg(long = "remapping", help = "Import remapping used when analysing, as [context:]prefix=target")]
+    remappings: Vec<String>,
+
+    #[arg(long = "evm-version", help = "EVM version used when analysing, the default one of the compiler otherwise")]
+    evm_version: Option<String>,
+
+    #[arg(long = "offline", default_value = "false", help = "Only use the installed solc versions, never download one")]
+    offline: bool,
+
+    #[arg(long = "solc", help = "A solc binary, or a directory of binaries, to use instead of the managed ones")]
+    solc: Option<String>,
+
+    #[arg(long = "default-version", help = "Solidity version requirement for the files without pragma, e.g. ^0.8.0")]
+    default_version: Option<String>,
+
+    #[arg(long = "no-cache", default_value = "false", help = "Always run solc, without reading nor writing the AST cache")]
+    no_cache: bool,
+
+    #[arg(long = "cache-dir", default_value = DEFAULT_CACHE_DIR, help = "Directory of the AST cache")]
+    cache_dir: String,
 }
 
-pub fn severity_to_string(severity: Option<Severity>) -> String {
-    match severity {
-        Some(Severity::ERROR) => format!("error").red(),
-        Some(Severity::WARNING) => format!("warning").yellow(),
-        Some(Severity::INFO) => format!("info").blue(),
-        Some(Severity::HINT) => format!("hint").green(),
-        _ => format!("error").red(),
+// Prints the fixes of a file and the diagnostics remaining once they are applied, which are returned.
+// A dry run prints the changes the fixes would make to the file instead, the remaining diagnostics pointing to lines
+// of the fixed content.
+fn print_fix_report(path: &str, result: Result<FixReport, LintError>, dry_run: bool) -> LintResult {
+    match result {
+        Ok(report) => {
+            if dry_run {
+                if report.applied > 0 {
+                    println!("{}: {} fix(es) available", path, report.applied);
+                    match std::fs::read_to_string(path) {
+                        Ok(content) => {
+                            let _ = write_diff(&mut std::io::stdout(), path, &content, &report.content, true);
+                        }
+                        Err(e) => println!("{}: {}", path, e),
+                    }
+                }
+                if !report.diags.is_empty() {
+                    println!("{}: {} diagnostic(s) would remain", path, report.diags.len());
+                }
+                return Ok(report.diags);
+            }
+            if report.applied > 0 {
+                if let Err(e) = std::fs::write(path, &report.content) {
+                    println!("{}: {}", path, e);
+                    return Err(LintError::IoError(e));
+                }
+                println!("{}: {} fix(es) applied", path, report.applied);
+            }
+            for diag in &report.diags {
+                let _ = write_diag(&mut std::io::stdout(), diag, true);
+            }
+            Ok(report.diags)
+        }
+        Err(e) => {
+            println!("{}", e);
+            Err(e)
+        }
     }
-    .to_string()
 }
 
-fn print_diag(diag: &solidhunter_lib::types::LintDiag) {
-    let mut padding : String = String::new();
-    if diag.range.start.line > 99 {
-        padding = " ".repeat(0).to_string();
-    } else if diag.range.start.line > 9 {
-        padding = " ".repeat(1).to_string();
-    } else {
-        padding = " ".repeat(2).to_string();
-    }
-    let line = diag.source_file_content.lines().nth((diag.range.start.line - 1) as usize).unwrap();
-
-    println!("\n{}: {}", severity_to_string(diag.severity), diag.message);
-    println!(
-        "  --> {}:{}:{}",
-        diag.uri,
-        diag.range.start.line,
-        diag.range.start.character,
-    );
-    println!(
-        "   |");
-    //TODO: add code to print
-    println!(
-        "{}{}|{}", diag.range.start.line,padding, line);
-    println!(
-        "   |{}{}", " ".repeat(diag.range.start.character as usize), "^".repeat(diag.range.length as usize));
-}
-
-fn lint_folder(args: Args) {
+fn detect_project(args: &Args) -> Option<Project> {
+    if args.no_project {
+        return None;
+    }
+    let path = match args.file_to_lint.is_empty() {
+        true => args.project_path.first().map_or(".", String::as_str),
+        false => args.file_to_lint.as_str(),
+    };
+    match Project::detect(std::path::Path::new(path)) {
+        Ok(project) => project,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(Status::ConfigError as i32);
+        }
+    }
+}
+
+// The paths given on the command line, otherwise the source folders of the project
+fn lint_roots(args: &Args, project: Option<&Project>) -> Vec<String> {
+    if !args.project_path.is_empty() {
+        return args.project_path.clone();
+    }
+    match project.map(Project::lint_roots) {
+        Some(roots) if !roots.is_empty() => roots.iter().map(|root| root.to_string_lossy().to_string()).collect(),
+        _ => vec![".".to_string()],
+    }
+}
+
+// The imports are resolved like in the project, the command line adding to its settings
+fn compile_options(args: &Args, project: Option<&Project>) -> CompileOptions {
+    let mut options = project.map(Project::compile_options).unwrap_or_default();
+    if let Some(base_path) = &args.base_path {
+        options.base_path = Some(PathBuf::from(base_path));
+    }
+    options.include_paths.extend(args.include_paths.iter().map(PathBuf::from));
+    options.remappings.extend(args.remappings.iter().cloned());
+    options.evm_version = args.evm_version.clone();
+    options
+}
+
+fn create_linter(args: &Args, project: Option<&Project>) -> SolidLinter {
     let mut linter: SolidLinter = SolidLinter::new();
-    linter.initalize(&args.rules_file);
-    let mut result = Vec::new();
-    for path in args.project_path {
-        result.append(&mut linter.parse_folder(path));
-    }
-    for res in result {
-        match res {
-            Ok(diags) => {
-                for diag in diags {
-                    print_diag(&diag);
-                }
-            }
+    match linter.initalize(&args.rules_file) {
+        Ok(()) => {}
+        Err(RulesError::NotFound { .. }) => {
+            eprintln!("Warning: rules file {} not found, using the default rules. Run `solidhunter --init` to create it.", args.rules_file);
+            linter.initalize_with_default_rules();
+        }
+        Err(e) => {
+            eprintln!("Invalid rules file: {}", e);
+            std::process::exit(Status::ConfigError as i32);
+        }
+    }
+    // The command line overrides the compiler settings of the rules file
+    let mut solc_config = linter.solc_config().clone();
+    solc_config.offline |= args.offline;
+    if let Some(solc) = &args.solc {
+        solc_config.solc_path = Some(PathBuf::from(solc));
+    }
+    if let Some(default_version) = &args.default_version {
+        solc_config.default_version = Some(default_version.clone());
+    }
+    if !args.no_cache {
+        linter.set_cache_config(Some(CacheConfig {
+            dir: PathBuf::from(&args.cache_dir),
+            ..CacheConfig::default()
+        }));
+    }
+    linter.set_solc_config(solc_config);
+    linter.set_report_unused_suppressions(args.report_unused_suppressions);
+    linter.set_jobs(args.jobs);
+    linter.set_use_gitignore(!args.no_gitignore);
+    linter.set_compile_options(compile_options(args, project));
+    if args.analyse {
+        linter.set_ast_mode(AstMode::Analyse);
+    }
+    if let Err(e) = linter.set_excludes(args.ignore_path.clone()) {
+        eprintln!("{}", e);
+        std::process::exit(Status::ConfigError as i32);
+    }
+    linter
+}
+
+fn fix(args: Args) -> Status {
+    let dry_run = !args.fix;
+    let project = detect_project(&args);
+    let mut linter = create_linter(&args, project.as_ref());
+
+    let mut results: BTreeMap<String, LintResult> = BTreeMap::new();
+    if !args.file_to_lint.is_empty() {
+        let result = linter.fix_file(args.file_to_lint.clone());
+        results.insert(args.file_to_lint.clone(), print_fix_report(&args.file_to_lint, result, dry_run));
+    } else {
+        for (file, result) in linter.fix_folders(&lint_roots(&args, project.as_ref())) {
+            let result = print_fix_report(&file, result, dry_run);
+            results.insert(file, result);
+        }
+    }
+
+    // The findings the fixes leave fail the run like when linting
+    let summary = Summary::new(&results);
+    println!("\n{}", summary);
+    exit_status(&summary, &args)
+}
+
+// -j only replaces the default format, keeping its former output: a single array of the diagnostics of all the files
+fn prints_diags_json(args: &Args) -> bool {
+    args.to_json && args.formats == [FormatOutput { format: OutputFormat::Text, output_file: None }]
+}
+
+fn create_reporters(args: &Args) -> Vec<Box<dyn Reporter>> {
+    if prints_diags_json(args) {
+        return vec![create_diags_json_reporter()];
+    }
+    let mut reporters = Vec::new();
+    for output in &args.formats {
+        match create_reporter(output.format, output.output_file.as_deref()) {
+            Ok(reporter) => reporters.push(reporter),
             Err(e) => {
-                println!("{}", e);
+                eprintln!("Cannot create {}: {}", output.output_file.as_deref().unwrap_or_default(), e);
+                std::process::exit(Status::ConfigError as i32);
             }
         }
     }
+    reporters
+}
+
+fn exit_status(summary: &Summary, args: &Args) -> Status {
+    if summary.compiler_failures > 0 {
+        return Status::CompilerError;
+    }
+    let too_many_warnings = args.max_warnings.is_some_and(|max_warnings| summary.warnings > max_warnings);
+    match summary.at_least(args.fail_on.into()) > 0 || too_many_warnings {
+        true => Status::Findings,
+        false => Status::Clean,
+    }
+}
+
+// Only the text output leaves room for the banner and the summary on the standard output
+fn prints_text(args: &Args) -> bool {
+    let printed: Vec<OutputFormat> = args.formats.iter()
+        .filter(|output| output.output_file.is_none())
+        .map(|output| output.format)
+        .collect();
+    printed == [OutputFormat::Text] && !prints_diags_json(args)
+}
+
+fn lint(args: Args, mut reporters: Vec<Box<dyn Reporter>>) -> Status {
+    let project = detect_project(&args);
+    let mut linter = create_linter(&args, project.as_ref());
+
+    let results: BTreeMap<String, LintResult> = match args.file_to_lint.is_empty() {
+        true => linter.parse_folders(&lint_roots(&args, project.as_ref())),
+        false => BTreeMap::from([(args.file_to_lint.clone(), linter.parse_file(args.file_to_lint.clone()))]),
+    };
+    for reporter in &mut reporters {
+        if let Err(e) = reporter.report(&results) {
+            eprintln!("Cannot write the report: {}", e);
+        }
+    }
+
+    let summary = Summary::new(&results);
+    match prints_text(&args) {
+        true => println!("\n{}", summary),
+        false => eprintln!("{}", summary),
+    }
+    exit_status(&summary, &args)
 }
 
 fn main() {
     let args = Args::parse();
 
-    if !args.to_json {
+    if args.schema {
+        match serde_json::to_string_pretty(&create_rules_schema()) {
+            Ok(j) => println!("{}", j),
+            Err(e) => println!("{}", e),
+        }
+        return;
+    }
+
+    if prints_text(&args) {
         println!();
         println!("SolidHunter: Fast and efficient Solidity linter");
         println!("By {} - v{} - GNU GPL v3", env!("CARGO_PKG_AUTHORS"), env!("CARGO_PKG_VERSION"));
@@ -109,48 +365,23 @@ fn main() {
     }
 
     if args.init {
+        if std::path::Path::new(&args.rules_file).exists() {
+            println!("Rules file {} already exists", args.rules_file);
+            return;
+        }
         println!("Initializing rules file...");
-        create_rules_file(".solidhunter.json");
-        println!("Done!");
+        match create_rules_file(&args.rules_file) {
+            Ok(()) => println!("Done!"),
+            Err(e) => println!("{}", e),
+        }
         return;
     }
 
-    if !args.to_json && args.file_to_lint == "" {
-        lint_folder(args);
-    }
-    else if args.file_to_lint != "" {
-        let mut linter: SolidLinter = SolidLinter::new();
-        linter.initalize(&args.rules_file);
-        
-        let result = linter.parse_file(args.file_to_lint);
-        if !args.to_json {
-            match result {
-                Ok(diags) => {
-                    for diag in diags {
-                        print_diag(&diag);
-                    }
-                }
-                Err(e) => {
-                    println!("{}", e);
-                }
-            }
-        } else {
-            match result {
-                Ok(diags) => {
-                    let json = serde_json::to_string_pretty(&diags);
-                    match json {
-                        Ok(j) => {
-                            println!("{}", j);
-                        }
-                        Err(e) => {
-                            println!("{}", e);
-                        }
-                    }
-                }
-                Err(e) => {
-                    println!("{}", e);
-                }
-            }
-        }
+    if args.fix || args.fix_dry_run {
+        let status = fix(args);
+        std::process::exit(status as i32);
     }
+    let reporters = create_reporters(&args);
+    let status = lint(args, reporters);
+    std::process::exit(status as i32);
 }
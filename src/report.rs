@@ -0,0 +1,443 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use clap::ValueEnum;
+use colored::Colorize;
+use serde_json::json;
+use solidhunter_lib::linter::has_solc_errors;
+use solidhunter_lib::types::{LintDiag, LintResult, Severity};
+use crate::sarif;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Jsonl,
+    Sarif,
+}
+
+/// An output format, and the file it is written to rather than the standard output, given as `<format>[=<file>]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormatOutput {
+    pub format: OutputFormat,
+    pub output_file: Option<String>,
+}
+
+impl FormatOutput {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (format, output_file) = match value.split_once('=') {
+            Some((_, "")) => return Err(format!("missing the file of \"{}\"", value)),
+            Some((format, output_file)) => (format, Some(output_file.to_string())),
+            None => (value, None),
+        };
+        let format = OutputFormat::from_str(format, true)?;
+        Ok(FormatOutput { format, output_file })
+    }
+}
+
+/// Outputs the results of the linted files, keyed by path, once they are all linted.
+pub trait Reporter {
+    fn report(&mut self, results: &BTreeMap<String, LintResult>) -> io::Result<()>;
+}
+
+/// Creates the reporter of `format`, writing to `output_file` or to the standard output.
+pub fn create_reporter(format: OutputFormat, output_file: Option<&str>) -> io::Result<Box<dyn Reporter>> {
+    let out: Box<dyn Write> = match output_file {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout()),
+    };
+    Ok(match format {
+        // Files don't get the terminal colors
+        OutputFormat::Text => Box::new(TextReporter { out, colored: output_file.is_none() }),
+        OutputFormat::Json => Box::new(JsonReporter { out }),
+        OutputFormat::Jsonl => Box::new(JsonlReporter { out }),
+        OutputFormat::Sarif => Box::new(SarifReporter { out }),
+    })
+}
+
+pub fn severity_to_string(severity: Option<Severity>, colored: bool) -> String {
+    let name = match severity {
+        Some(Severity::ERROR) | None => "error",
+        Some(Severity::WARNING) => "warning",
+        Some(Severity::INFO) => "info",
+        Some(Severity::HINT) => "hint",
+    };
+    if !colored {
+        return name.to_string();
+    }
+    match severity {
+        Some(Severity::ERROR) | None => name.red(),
+        Some(Severity::WARNING) => name.yellow(),
+        Some(Severity::INFO) => name.blue(),
+        Some(Severity::HINT) => name.green(),
+    }
+    .to_string()
+}
+
+/// Writes `diag` with the line it points to.
+pub fn write_diag(out: &mut dyn Write, diag: &LintDiag, colored: bool) -> io::Result<()> {
+    let padding = match diag.range.start.line {
+        line if line > 99 => "",
+        line if line > 9 => " ",
+        _ => "  ",
+    };
+    let line = diag.source_file_content.lines().nth((diag.range.start.line - 1) as usize).unwrap_or_default();
+
+    writeln!(out, "\n{}: {}", severity_to_string(diag.severity, colored), diag.message)?;
+    writeln!(out, "  --> {}:{}:{}", diag.uri, diag.range.start.line, diag.range.start.character)?;
+    writeln!(out, "   |")?;
+    writeln!(out, "{}{}|{}", diag.range.start.line, padding, line)?;
+    writeln!(out, "   |{}{}", " ".repeat(diag.range.start.character as usize), "^".repeat(diag.range.length as usize))
+}
+
+// The lines of context around the changes of a diff
+const DIFF_CONTEXT: usize = 3;
+
+#[derive(Clone, Copy, PartialEq)]
+enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+// The line diff of `old` and `new`, from their longest common subsequence
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = match old[i] == new[j] {
+                true => lcs[i + 1][j + 1] + 1,
+                false => lcs[i + 1][j].max(lcs[i][j + 1]),
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(DiffLine::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
+/// Writes the changes from `old` to `new` content of the file at `path` as a unified diff.
+pub fn write_diff(out: &mut dyn Write, path: &str, old: &str, new: &str, colored: bool) -> io::Result<()> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let lines = diff_lines(&old_lines, &new_lines);
+    let changes: Vec<usize> = (0..lines.len()).filter(|idx| !matches!(lines[*idx], DiffLine::Same(_))).collect();
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(out, "--- a/{}\n+++ b/{}", path, path)?;
+    // The line numbers in the old and new content before each diff line
+    let mut positions = Vec::with_capacity(lines.len() + 1);
+    let (mut old_line, mut new_line) = (0, 0);
+    for line in &lines {
+        positions.push((old_line, new_line));
+        match line {
+            DiffLine::Same(_) => (old_line, new_line) = (old_line + 1, new_line + 1),
+            DiffLine::Removed(_) => old_line += 1,
+            DiffLine::Added(_) => new_line += 1,
+        }
+    }
+    positions.push((old_line, new_line));
+
+    let mut idx = 0;
+    while idx < changes.len() {
+        // Changes closer than twice the context share a hunk
+        let start = changes[idx].saturating_sub(DIFF_CONTEXT);
+        let mut end = changes[idx] + 1;
+        while idx + 1 < changes.len() && changes[idx + 1] <= end + 2 * DIFF_CONTEXT {
+            idx += 1;
+            end = changes[idx] + 1;
+        }
+        let end = (end + DIFF_CONTEXT).min(lines.len());
+        idx += 1;
+
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        // An empty range starts at the line before it
+        let first_line = |start: usize, count: usize| if count == 0 { start } else { start + 1 };
+        writeln!(out, "@@ -{},{} +{},{} @@", first_line(old_start, old_end - old_start), old_end - old_start,
+                 first_line(new_start, new_end - new_start), new_end - new_start)?;
+        for line in &lines[start..end] {
+            match (line, colored) {
+                (DiffLine::Same(text), _) => writeln!(out, " {}", text)?,
+                (DiffLine::Removed(text), false) => writeln!(out, "-{}", text)?,
+                (DiffLine::Added(text), false) => writeln!(out, "+{}", text)?,
+                (DiffLine::Removed(text), true) => writeln!(out, "{}", format!("-{}", text).red())?,
+                (DiffLine::Added(text), true) => writeln!(out, "{}", format!("+{}", text).green())?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The diagnostics as in a terminal, with the source line they point to.
+pub struct TextReporter<W: Write> {
+    out: W,
+    colored: bool,
+}
+
+impl<W: Write> Reporter for TextReporter<W> {
+    fn report(&mut self, results: &BTreeMap<String, LintResult>) -> io::Result<()> {
+        for (path, result) in results {
+            match result {
+                Ok(diags) => {
+                    for diag in diags {
+                        write_diag(&mut self.out, diag, self.colored)?;
+                    }
+                }
+                Err(e) => writeln!(self.out, "{}: {}", path, e)?,
+            }
+        }
+        self.out.flush()
+    }
+}
+
+/// An array with an object per file, holding either its `diags` or the `error` it couldn't be linted with.
+pub struct JsonReporter<W: Write> {
+    out: W,
+}
+
+impl<W: Write> Reporter for JsonReporter<W> {
+    fn report(&mut self, results: &BTreeMap<String, LintResult>) -> io::Result<()> {
+        let files: Vec<serde_json::Value> = results.iter()
+            .map(|(path, result)| match result {
+                Ok(diags) => json!({ "path": path, "diags": diags }),
+                Err(e) => json!({ "path": path, "error": e.to_string() }),
+            })
+            .collect();
+        serde_json::to_writer_pretty(&mut self.out, &files)?;
+        writeln!(self.out)?;
+        self.out.flush()
+    }
+}
+
+/// The output of `-j`: a single array of the diagnostics of all the files, the files that couldn't be linted being
+/// reported on the standard error.
+pub struct DiagsJsonReporter<W: Write> {
+    out: W,
+}
+
+/// Creates the reporter of `-j`, printing to the standard output.
+pub fn create_diags_json_reporter() -> Box<dyn Reporter> {
+    Box::new(DiagsJsonReporter { out: io::stdout() })
+}
+
+impl<W: Write> Reporter for DiagsJsonReporter<W> {
+    fn report(&mut self, results: &BTreeMap<String, LintResult>) -> io::Result<()> {
+        let mut diags = Vec::new();
+        for (path, result) in results {
+            match result {
+                Ok(file_diags) => diags.extend(file_diags),
+                Err(e) => eprintln!("{}: {}", path, e),
+            }
+        }
+        serde_json::to_writer_pretty(&mut self.out, &diags)?;
+        writeln!(self.out)?;
+        self.out.flush()
+    }
+}
+
+/// A line per diagnostic, and a `{"uri", "error"}` line per file that couldn't be linted.
+pub struct JsonlReporter<W: Write> {
+    out: W,
+}
+
+impl<W: Write> Reporter for JsonlReporter<W> {
+    fn report(&mut self, results: &BTreeMap<String, LintResult>) -> io::Result<()> {
+        for (path, result) in results {
+            match result {
+                Ok(diags) => {
+                    for diag in diags {
+                        serde_json::to_writer(&mut self.out, diag)?;
+                        writeln!(self.out)?;
+                    }
+                }
+                Err(e) => {
+                    serde_json::to_writer(&mut self.out, &json!({ "uri": path, "error": e.to_string() }))?;
+                    writeln!(self.out)?;
+                }
+            }
+        }
+        self.out.flush()
+    }
+}
+
+/// A SARIF 2.1.0 log, for code scanning tools.
+pub struct SarifReporter<W: Write> {
+    out: W,
+}
+
+impl<W: Write> Reporter for SarifReporter<W> {
+    fn report(&mut self, results: &BTreeMap<String, LintResult>) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut self.out, &sarif::to_sarif(results))?;
+        writeln!(self.out)?;
+        self.out.flush()
+    }
+}
+
+/// Number of diagnostics per severity, and of files solc failed on.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+    pub hints: usize,
+    /// Files that couldn't be linted, or that solc reported errors for.
+    pub compiler_failures: usize,
+}
+
+impl Summary {
+    pub fn new(results: &BTreeMap<String, LintResult>) -> Self {
+        let mut summary = Summary::default();
+        for result in results.values() {
+            let diags = match result {
+                Ok(diags) => diags,
+                Err(_) => {
+                    summary.compiler_failures += 1;
+                    continue;
+                }
+            };
+            if has_solc_errors(diags) {
+                summary.compiler_failures += 1;
+            }
+            for diag in diags {
+                match diag.severity {
+                    Some(Severity::ERROR) | None => summary.errors += 1,
+                    Some(Severity::WARNING) => summary.warnings += 1,
+                    Some(Severity::INFO) => summary.infos += 1,
+                    Some(Severity::HINT) => summary.hints += 1,
+                }
+            }
+        }
+        summary
+    }
+
+    /// Number of diagnostics at least as severe as `severity`.
+    pub fn at_least(&self, severity: Severity) -> usize {
+        [(Severity::ERROR, self.errors), (Severity::WARNING, self.warnings), (Severity::INFO, self.infos), (Severity::HINT, self.hints)]
+            .into_iter()
+            .filter(|(diag_severity, _)| (*diag_severity as u8) <= (severity as u8))
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
+
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} error(s), {} warning(s), {} info(s), {} hint(s)", self.errors, self.warnings, self.infos, self.hints)?;
+        if self.compiler_failures > 0 {
+            write!(f, ", {} file(s) failed to compile", self.compiler_failures)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use solidhunter_lib::types::{LintError, NumberOrString, Position, Range};
+
+    fn results() -> BTreeMap<String, LintResult> {
+        let diag = LintDiag {
+            range: Range {
+                start: Position { line: 2, character: 11 },
+                end: Position { line: 2, character: 14 },
+                length: 3,
+            },
+            severity: Some(Severity::WARNING),
+            code: Some(NumberOrString::String("quotes".to_string())),
+            source: None,
+            message: "Use double quotes instead of single quote".to_string(),
+            uri: "src/A.sol".to_string(),
+            source_file_content: "pragma solidity 0.8.0;\nstring a = 'a';\n".to_string(),
+            fix: None,
+            suggestion: None,
+        };
+        let mut results = BTreeMap::new();
+        results.insert("src/A.sol".to_string(), Ok(vec![diag]));
+        results.insert("src/B.sol".to_string(), Err(LintError::LinterError("cannot read the file".to_string())));
+        results
+    }
+
+    #[test]
+    fn test_parse_format_output() {
+        assert_eq!(FormatOutput::parse("sarif=out.sarif"), Ok(FormatOutput { format: OutputFormat::Sarif, output_file: Some("out.sarif".to_string()) }));
+        assert_eq!(FormatOutput::parse("text"), Ok(FormatOutput { format: OutputFormat::Text, output_file: None }));
+        assert!(FormatOutput::parse("sarif=").is_err());
+        assert!(FormatOutput::parse("xml=out.xml").is_err());
+    }
+
+    #[test]
+    fn test_text_reporter() {
+        let mut reporter = TextReporter { out: Vec::new(), colored: false };
+        reporter.report(&results()).unwrap();
+
+        assert_eq!(String::from_utf8(reporter.out).unwrap(), "\nwarning: Use double quotes instead of single quote\n  --> src/A.sol:2:11\n   |\n2  |string a = 'a';\n   |           ^^^\nsrc/B.sol: LintError: cannot read the file\n");
+    }
+
+    #[test]
+    fn test_json_reporters() {
+        let mut reporter = JsonReporter { out: Vec::new() };
+        reporter.report(&results()).unwrap();
+        let files: Value = serde_json::from_slice(&reporter.out).unwrap();
+
+        assert_eq!(files[0]["path"], "src/A.sol");
+        assert_eq!(files[0]["diags"][0]["message"], "Use double quotes instead of single quote");
+        assert_eq!(files[1]["error"], "LintError: cannot read the file");
+
+        let mut reporter = DiagsJsonReporter { out: Vec::new() };
+        reporter.report(&results()).unwrap();
+        let diags: Value = serde_json::from_slice(&reporter.out).unwrap();
+
+        assert_eq!(diags.as_array().map(Vec::len), Some(1));
+        assert_eq!(diags[0]["uri"], "src/A.sol");
+
+        let mut reporter = JsonlReporter { out: Vec::new() };
+        reporter.report(&results()).unwrap();
+        let lines: Vec<Value> = String::from_utf8(reporter.out).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["uri"], "src/A.sol");
+        assert_eq!(lines[0]["range"]["start"]["line"], 2);
+        assert_eq!(lines[1], json!({ "uri": "src/B.sol", "error": "LintError: cannot read the file" }));
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = Summary::new(&results());
+
+        assert_eq!(summary, Summary { warnings: 1, compiler_failures: 1, ..Summary::default() });
+        assert_eq!(summary.at_least(Severity::ERROR), 0);
+        assert_eq!(summary.at_least(Severity::INFO), 1);
+        assert_eq!(summary.to_string(), "0 error(s), 1 warning(s), 0 info(s), 0 hint(s), 1 file(s) failed to compile");
+    }
+
+    #[test]
+    fn test_write_diff() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+        let mut out = Vec::new();
+        write_diff(&mut out, "src/A.sol", old, new, false).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "--- a/src/A.sol\n+++ b/src/A.sol\n\
+            @@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n\
+            @@ -9,4 +9,3 @@\n i\n j\n k\n-l\n");
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::{BTreeMap, HashMap};
+use serde_json::{json, Value};
+use solidhunter_lib::rules::factory::RuleFactory;
+use solidhunter_lib::rules::rule_impl::create_preset;
+use solidhunter_lib::types::{LintDiag, LintResult, NumberOrString, Severity};
+use solidhunter_lib::{offset_from_range, range_from_offset};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/astrodevs-labs/solidhunter";
+
+fn severity_to_level(severity: Option<Severity>) -> &'static str {
+    match severity {
+        Some(Severity::ERROR) | None => "error",
+        Some(Severity::WARNING) => "warning",
+        Some(Severity::INFO) | Some(Severity::HINT) => "note",
+    }
+}
+
+fn to_uri(path: &str) -> String {
+    path.replace('\\', "/").trim_start_matches("./").to_string()
+}
+
+// Builds the `tool.driver.rules` catalogue from every registered rule, and the index of each id in it
+fn rules_catalogue() -> (Vec<Value>, HashMap<String, usize>) {
+    let mut factory = RuleFactory::new();
+    factory.register_rules();
+    // The rules off by default are given the severity the `all` preset enables them with
+    let defaults: HashMap<String, Severity> = create_preset("all")
+        .unwrap_or_default()
+        .into_iter()
+        .map(|rule| (rule.id, rule.severity))
+        .collect();
+
+    let mut rules = Vec::new();
+    let mut indexes = HashMap::new();
+    for (idx, id) in factory.get_rule_ids().into_iter().enumerate() {
+        rules.push(json!({
+            "id": id,
+            "defaultConfiguration": {
+                "level": severity_to_level(Some(defaults.get(&id).copied().unwrap_or(Severity::WARNING))),
+            },
+        }));
+        indexes.insert(id, idx);
+    }
+    (rules, indexes)
+}
+
+fn to_result(diag: &LintDiag, indexes: &HashMap<String, usize>) -> Value {
+    let content = &diag.source_file_content;
+    let offset = offset_from_range(content, &diag.range).min(content.len());
+    let end = range_from_offset(content, offset, diag.range.length as usize).end;
+    let rule_id = match &diag.code {
+        Some(NumberOrString::String(code)) => code.clone(),
+        Some(NumberOrString::Number(code)) => code.to_string(),
+        None => "solidhunter".to_string(),
+    };
+
+    let mut result = json!({
+        "ruleId": rule_id,
+        "level": severity_to_level(diag.severity),
+        "message": { "text": diag.message },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": to_uri(&diag.uri) },
+                // SARIF lines and columns are 1-based
+                "region": {
+                    "startLine": diag.range.start.line,
+                    "startColumn": diag.range.start.character + 1,
+                    "endLine": end.line,
+                    "endColumn": end.character + 1,
+                },
+            },
+        }],
+    });
+    if let Some(idx) = indexes.get(&rule_id) {
+        result["ruleIndex"] = json!(idx);
+    }
+    result
+}
+
+/// Converts lint results, keyed by file path, to a SARIF 2.1.0 log.
+/// Files which could not be linted are reported as tool execution notifications.
+pub fn to_sarif(results: &BTreeMap<String, LintResult>) -> Value {
+    let (rules, indexes) = rules_catalogue();
+    let mut sarif_results = Vec::new();
+    let mut notifications = Vec::new();
+
+    for (path, result) in results {
+        match result {
+            Ok(diags) => sarif_results.extend(diags.iter().map(|diag| to_result(diag, &indexes))),
+            Err(e) => notifications.push(json!({
+                "level": "error",
+                "message": { "text": e.to_string() },
+                "locations": [{
+                    "physicalLocation": { "artifactLocation": { "uri": to_uri(path) } },
+                }],
+            })),
+        }
+    }
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "solidhunter",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": INFORMATION_URI,
+                    "rules": rules,
+                },
+            },
+            "invocations": [{
+                "executionSuccessful": notifications.is_empty(),
+                "toolExecutionNotifications": notifications,
+            }],
+            "results": sarif_results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solidhunter_lib::types::{Position, Range};
+
+    #[test]
+    fn test_to_sarif() {
+        let content = "pragma solidity 0.8.0;\nstring a = 'a';\n";
+        let diag = LintDiag {
+            range: Range {
+                start: Position { line: 2, character: 11 },
+                end: Position { line: 2, character: 12 },
+                length: 1,
+            },
+            severity: Some(Severity::WARNING),
+            code: Some(NumberOrString::String("quotes".to_string())),
+            source: None,
+            message: "Use double quotes instead of single quote".to_string(),
+            uri: "./src/Test.sol".to_string(),
+            source_file_content: content.to_string(),
+            fix: None,
+            suggestion: None,
+        };
+        let mut results = BTreeMap::new();
+        results.insert("./src/Test.sol".to_string(), Ok(vec![diag]));
+
+        let sarif = to_sarif(&results);
+        let run = &sarif["runs"][0];
+        let result = &run["results"][0];
+        let rule_idx = result["ruleIndex"].as_u64().unwrap() as usize;
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"][rule_idx]["id"], "quotes");
+        assert_eq!(result["level"], "warning");
+        assert_eq!(result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "src/Test.sol");
+        assert_eq!(result["locations"][0]["physicalLocation"]["region"]["startColumn"], 12);
+        assert_eq!(result["locations"][0]["physicalLocation"]["region"]["endColumn"], 13);
+    }
+
+    #[test]
+    fn test_rules_catalogue_levels() {
+        let (rules, indexes) = rules_catalogue();
+        let level = |id: &str| rules[indexes[id]]["defaultConfiguration"]["level"].clone();
+
+        assert_eq!(level("quotes"), "error");
+        // Off by default
+        assert_eq!(level("no-unused-files"), "warning");
+    }
+}